@@ -6,33 +6,35 @@ use std::hint::black_box;
 ////////////////////////////////////////
 
 pub fn generate(c: &mut Criterion) {
-    c.bench_function("generate", |b| b.iter(|| Grid::generate(black_box(None))));
+    c.bench_function("generate", |b| {
+        b.iter(|| Grid::generate(black_box(None), black_box(None), &mut rand::rng()))
+    });
 }
 
 pub fn solve_10(c: &mut Criterion) {
-    let mut grid = Grid::generate(None);
-    grid.remove_random_values(10);
+    let mut grid = Grid::generate(None, None, &mut rand::rng()).unwrap();
+    grid.remove_random_values(10, &mut rand::rng()).unwrap();
 
     c.bench_function("solve 10", |b| b.iter(|| grid.solve()));
 }
 
 pub fn solve_30(c: &mut Criterion) {
-    let mut grid = Grid::generate(None);
-    grid.remove_random_values(30);
+    let mut grid = Grid::generate(None, None, &mut rand::rng()).unwrap();
+    grid.remove_random_values(30, &mut rand::rng()).unwrap();
 
     c.bench_function("solve 30", |b| b.iter(|| grid.solve()));
 }
 
 pub fn solve_50(c: &mut Criterion) {
-    let mut grid = Grid::generate(None);
-    grid.remove_random_values(50);
+    let mut grid = Grid::generate(None, None, &mut rand::rng()).unwrap();
+    grid.remove_random_values(50, &mut rand::rng()).unwrap();
 
     c.bench_function("solve 50", |b| b.iter(|| grid.solve()));
 }
 
 pub fn solve_64(c: &mut Criterion) {
-    let mut grid = Grid::generate(None);
-    grid.remove_random_values(64);
+    let mut grid = Grid::generate(None, None, &mut rand::rng()).unwrap();
+    grid.remove_random_values(64, &mut rand::rng()).unwrap();
 
     c.bench_function("solve 64", |b| b.iter(|| grid.solve()));
 }
@@ -47,12 +47,13 @@ pub fn main() {
 ////////////////////
 
 fn benchmark_one_generate() -> Duration {
-    time!(|| Grid::generate(None))
+    time!(|| Grid::generate(None, None, &mut rand::rng()))
 }
 
-fn benchmark_one_solver(nb_to_remove: u8) -> Duration {
-    let mut grid = Grid::generate(None);
-    grid.remove_random_values(nb_to_remove);
+fn benchmark_one_solver(nb_to_remove: u16) -> Duration {
+    let mut grid = Grid::generate(None, None, &mut rand::rng()).unwrap();
+    grid.remove_random_values(nb_to_remove, &mut rand::rng())
+        .unwrap();
 
     time!(|| grid.solve())
 }
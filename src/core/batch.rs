@@ -0,0 +1,222 @@
+use super::{
+    error::SabitaError,
+    file::{is_line_file, parse_line, read, read_collection_lines, values_to_line},
+    grid::{Grid, GridValues},
+    solver::Solutions,
+};
+
+use std::{
+    fmt, fs,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+////////////////////////////////////////
+
+/// Number of puzzles a thread takes at once from the shared queue
+const CHUNK_SIZE: usize = 64;
+
+/// Written after a puzzle left unsolved in the solutions of a collection
+const UNSOLVABLE_MARK: &str = "unsolvable";
+const MULTIPLE_MARK: &str = "multiple";
+const UNPARSABLE_MARK: &str = "unparsable";
+
+////////////////////////////////////////
+
+/// Result of solving one puzzle of a batch
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchOutcome {
+    Solved(GridValues),
+    /// No solution, the puzzle breaking a rule included
+    Unsolvable,
+    /// More than one solution
+    Multiple,
+}
+
+//////////
+
+/// Counts of a solved batch, and the time it took
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchReport {
+    pub solved: usize,
+    pub unsolvable: usize,
+    pub multiple: usize,
+    /// Line number, starting from 1, of each puzzle of the file which could not be read
+    pub unparsable: Vec<usize>,
+    pub duration: Duration,
+}
+
+impl BatchReport {
+    /// Counts of the outcomes, without any duration
+    pub fn from_outcomes(outcomes: &[BatchOutcome]) -> Self {
+        let mut report = BatchReport::default();
+
+        for outcome in outcomes {
+            match outcome {
+                BatchOutcome::Solved(_) => report.solved += 1,
+                BatchOutcome::Unsolvable => report.unsolvable += 1,
+                BatchOutcome::Multiple => report.multiple += 1,
+            }
+        }
+
+        report
+    }
+
+    pub fn total(&self) -> usize {
+        self.solved + self.unsolvable + self.multiple + self.unparsable.len()
+    }
+}
+
+impl fmt::Display for BatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} puzzles in {:.3}s: {} solved, {} unsolvable, {} with multiple solutions, {} unparsable",
+            self.total(),
+            self.duration.as_secs_f64(),
+            self.solved,
+            self.unsolvable,
+            self.multiple,
+            self.unparsable.len()
+        )?;
+
+        if !self.unparsable.is_empty() {
+            let lines: Vec<String> = self
+                .unparsable
+                .iter()
+                .map(|line| line.to_string())
+                .collect();
+
+            write!(f, " (lines {})", lines.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////
+
+/// Solves every puzzle on every CPU core, the outcomes following the order of the puzzles
+pub fn solve_many(puzzles: &[GridValues]) -> Vec<BatchOutcome> {
+    let nb_threads = thread::available_parallelism().map_or(1, |nb| nb.get());
+    let next_chunk = AtomicUsize::new(0);
+
+    let mut solved: Vec<(usize, BatchOutcome)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..nb_threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut outcomes = vec![];
+
+                    loop {
+                        let start = next_chunk.fetch_add(CHUNK_SIZE, Ordering::Relaxed);
+
+                        if start >= puzzles.len() {
+                            break;
+                        }
+
+                        let end = (start + CHUNK_SIZE).min(puzzles.len());
+
+                        for (index, values) in puzzles[start..end].iter().enumerate() {
+                            outcomes.push((start + index, solve_one(values)));
+                        }
+                    }
+
+                    outcomes
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+
+    solved.sort_by_key(|(index, _)| *index);
+
+    solved.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+/// Solves every puzzle of a collection file, then writes a line per puzzle in the same order:
+/// its solution, or the puzzle followed by `unsolvable`, `multiple` or `unparsable`
+/// A line which can not be read is reported without stopping the others
+pub fn solve_collection(input: String, output: String) -> Result<BatchReport, SabitaError> {
+    let start = Instant::now();
+
+    // Puzzles along with their line, those which can not be read being kept as written
+    let entries: Vec<(usize, Result<GridValues, String>)> = match is_line_file(input.clone())? {
+        true => read_collection_lines(input)?
+            .into_iter()
+            .map(|(number, text)| (number, parse_line(&text).map_err(|_| text)))
+            .collect(),
+        false => vec![(1, Ok(read(input)?))],
+    };
+
+    let puzzles: Vec<GridValues> = entries
+        .iter()
+        .filter_map(|(_, puzzle)| puzzle.as_ref().ok().cloned())
+        .collect();
+    let outcomes = solve_many(&puzzles);
+
+    let mut report = BatchReport::from_outcomes(&outcomes);
+    let mut lines = vec![];
+    let mut solved = puzzles.iter().zip(outcomes.iter());
+
+    for (number, puzzle) in entries.iter() {
+        match puzzle {
+            Ok(_) => {
+                let (values, outcome) = solved.next().unwrap();
+                lines.push(outcome_line(values, outcome));
+            }
+            Err(text) => {
+                report.unparsable.push(*number);
+                lines.push(format!("{text} {UNPARSABLE_MARK}\n"));
+            }
+        }
+    }
+
+    fs::write(&output, lines.concat()).map_err(|error| SabitaError::Io {
+        path: output,
+        error,
+    })?;
+
+    Ok(BatchReport {
+        duration: start.elapsed(),
+        ..report
+    })
+}
+
+/// Lines written for the outcomes of a batch, each one ending with a new line
+pub fn outcomes_to_lines(puzzles: &[GridValues], outcomes: &[BatchOutcome]) -> Vec<String> {
+    puzzles
+        .iter()
+        .zip(outcomes)
+        .map(|(values, outcome)| outcome_line(values, outcome))
+        .collect()
+}
+
+////////////////////
+
+fn outcome_line(values: &GridValues, outcome: &BatchOutcome) -> String {
+    match outcome {
+        BatchOutcome::Solved(solution) => format!("{}\n", values_to_line(solution)),
+        BatchOutcome::Unsolvable => format!("{} {UNSOLVABLE_MARK}\n", values_to_line(values)),
+        BatchOutcome::Multiple => format!("{} {MULTIPLE_MARK}\n", values_to_line(values)),
+    }
+}
+
+/// Looks for a second solution to tell a unique one apart
+fn solve_one(values: &GridValues) -> BatchOutcome {
+    let Ok(grid) = Grid::new(values.clone()) else {
+        return BatchOutcome::Unsolvable;
+    };
+
+    let mut solutions = Solutions::from_board(grid.board());
+
+    match (solutions.next(), solutions.next()) {
+        (Some(solution), None) => BatchOutcome::Solved(solution),
+        (Some(_), Some(_)) => BatchOutcome::Multiple,
+        (None, _) => BatchOutcome::Unsolvable,
+    }
+}
@@ -0,0 +1,276 @@
+use super::{
+    constants::{LENGTH_DIMENSION, TO_BE_SOLVED},
+    constraint::Constraint,
+    error::SabitaError,
+    grid::{check_dimensions, wrong_number_of_lines, BoxLocation, GridValues},
+    layout::Layout,
+};
+
+use std::sync::Arc;
+
+////////////////////////////////////////
+
+const LENGTH: usize = LENGTH_DIMENSION as usize;
+
+/// Every value from 1 to 9 set, bit `n - 1` standing for value `n`
+pub const ALL_CANDIDATES: Candidates = (1 << LENGTH_DIMENSION) - 1;
+
+/// Bit mask of the values of a grid, bit `n - 1` standing for value `n`
+pub type Candidates = u32;
+
+/// Flat representation of a grid made for solving
+/// Each line, column and region keeps the mask of the values it already holds, so checking or
+/// updating a candidate never has to walk the grid
+/// Variant constraints are consulted on top of them each time the candidates of a box are listed
+#[derive(Debug, Clone)]
+pub struct Board {
+    layout: Layout,
+    boxes: Vec<u8>,
+    lines: Vec<Candidates>,
+    columns: Vec<Candidates>,
+    regions: Vec<Candidates>,
+    constraints: Vec<Arc<dyn Constraint>>,
+}
+
+////////////////////
+
+impl Board {
+    //////////
+    // Constructors
+
+    /// Board of the usual layout for the size of the grid
+    /// Fails when the grid has no usual layout for its number of lines or a value out of bound
+    pub fn new(values: &GridValues) -> Result<Self, SabitaError> {
+        let layout = Layout::for_size(values.len()).ok_or_else(|| wrong_number_of_lines(values))?;
+        check_dimensions(values, &layout)?;
+
+        Ok(Board::with_layout(values, &layout))
+    }
+
+    pub fn with_layout(values: &GridValues, layout: &Layout) -> Self {
+        let size = layout.size();
+
+        let mut board = Board {
+            layout: layout.clone(),
+            boxes: vec![TO_BE_SOLVED; layout.nb_boxes()],
+            lines: vec![0; size],
+            columns: vec![0; size],
+            regions: vec![0; size],
+            constraints: vec![],
+        };
+
+        for (line, row) in values.iter().enumerate() {
+            for (column, value) in row.iter().enumerate() {
+                if *value != TO_BE_SOLVED {
+                    board.place(line * size + column, *value);
+                }
+            }
+        }
+
+        board
+    }
+
+    /// Board without any value
+    pub fn empty(layout: &Layout) -> Self {
+        Board::with_layout(&vec![], layout)
+    }
+
+    /// Same board, its candidates also following the given constraints
+    pub fn with_constraints(mut self, constraints: &[Arc<dyn Constraint>]) -> Self {
+        self.constraints = constraints.to_vec();
+        self
+    }
+
+    //////////
+    // Accessors
+
+    pub fn get(&self, index: usize) -> u8 {
+        self.boxes[index]
+    }
+
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    pub fn constraints(&self) -> &[Arc<dyn Constraint>] {
+        &self.constraints
+    }
+
+    /// Every value of the grid
+    pub fn all_candidates(&self) -> Candidates {
+        all_candidates(self.layout.size())
+    }
+
+    pub fn get_values(&self) -> GridValues {
+        self.boxes
+            .chunks(self.layout.size())
+            .map(|line| line.to_vec())
+            .collect()
+    }
+
+    //////////
+    // Methods
+
+    /// Values that can go in the box without repeating one of its line, column or region, nor
+    /// breaking one of the constraints of the board
+    pub fn candidates(&self, index: usize) -> Candidates {
+        let (line, column, region) = self.coordinates(index);
+
+        let candidates = !(self.lines[line] | self.columns[column] | self.regions[region])
+            & self.all_candidates();
+
+        self.constraints
+            .iter()
+            .fold(candidates, |candidates, constraint| match candidates {
+                0 => 0,
+                _ => constraint.prune(self, index, candidates),
+            })
+    }
+
+    pub fn can_place(&self, index: usize, value: u8) -> bool {
+        self.candidates(index) & value_to_mask(value) != 0
+    }
+
+    pub fn place(&mut self, index: usize, value: u8) {
+        let (line, column, region) = self.coordinates(index);
+        let mask = value_to_mask(value);
+
+        self.boxes[index] = value;
+        self.lines[line] |= mask;
+        self.columns[column] |= mask;
+        self.regions[region] |= mask;
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        let value = self.boxes[index];
+
+        if value == TO_BE_SOLVED {
+            return;
+        }
+
+        let (line, column, region) = self.coordinates(index);
+        let mask = !value_to_mask(value);
+
+        self.boxes[index] = TO_BE_SOLVED;
+        self.lines[line] &= mask;
+        self.columns[column] &= mask;
+        self.regions[region] &= mask;
+    }
+
+    /// Indices of the boxes still to be solved
+    pub fn missing_indices(&self) -> Vec<usize> {
+        (0..self.boxes.len())
+            .filter(|&index| self.boxes[index] == TO_BE_SOLVED)
+            .collect()
+    }
+
+    /// Location of a box given by its flat index
+    pub fn location(&self, index: usize) -> BoxLocation {
+        let (line, column, region) = self.coordinates(index);
+
+        BoxLocation {
+            line,
+            column,
+            region: region as u8,
+        }
+    }
+
+    /// Missing box having the fewest candidates, along with them
+    /// Returns None when the board is complete
+    pub fn most_constrained(&self) -> Option<(usize, Candidates)> {
+        let mut best: Option<(usize, Candidates)> = None;
+
+        for index in 0..self.boxes.len() {
+            if self.boxes[index] != TO_BE_SOLVED {
+                continue;
+            }
+
+            let candidates = self.candidates(index);
+
+            let is_better = match best {
+                Some((_, best_candidates)) => {
+                    candidates.count_ones() < best_candidates.count_ones()
+                }
+                None => true,
+            };
+
+            if is_better {
+                best = Some((index, candidates));
+
+                if candidates.count_ones() <= 1 {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Flat index of a box into its (line, column, region)
+    fn coordinates(&self, index: usize) -> (usize, usize, usize) {
+        let size = self.layout.size();
+
+        (
+            index / size,
+            index % size,
+            self.layout.region_of_index(index) as usize,
+        )
+    }
+}
+
+////////////////////
+
+/// Flat index of a box of a standard grid into its (line, column, region)
+pub fn index_to_coordinates(index: usize) -> (usize, usize, usize) {
+    let line = index / LENGTH;
+    let column = index % LENGTH;
+    let region = (line / 3) * 3 + column / 3;
+
+    (line, column, region)
+}
+
+pub fn index_to_location(index: usize) -> BoxLocation {
+    let (line, column, region) = index_to_coordinates(index);
+
+    BoxLocation {
+        line,
+        column,
+        region: region as u8,
+    }
+}
+
+pub fn location_to_index(location: &BoxLocation) -> usize {
+    location.line * LENGTH + location.column
+}
+
+pub fn value_to_mask(value: u8) -> Candidates {
+    1 << (value - 1)
+}
+
+/// Mask of every value of a grid of the given size
+pub fn all_candidates(size: usize) -> Candidates {
+    ((1_u64 << size) - 1) as Candidates
+}
+
+/// Values held by the mask, ascending
+pub fn mask_to_values(mut mask: Candidates) -> Vec<u8> {
+    let mut values = vec![];
+
+    while let Some(value) = pop_lowest(&mut mask) {
+        values.push(value);
+    }
+
+    values
+}
+
+/// Takes the smallest value out of the mask
+pub fn pop_lowest(mask: &mut Candidates) -> Option<u8> {
+    if *mask == 0 {
+        return None;
+    }
+
+    let value = mask.trailing_zeros() as u8 + 1;
+    *mask &= *mask - 1;
+
+    Some(value)
+}
@@ -0,0 +1,221 @@
+use super::{
+    constants::{LENGTH_DIMENSION, TO_BE_SOLVED},
+    grid::GridValues,
+    transform::Transformation,
+};
+
+////////////////////////////////////////
+
+const LENGTH: usize = LENGTH_DIMENSION as usize;
+const BAND_SIZE: usize = 3;
+
+const ORDERS: [[usize; BAND_SIZE]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+type Line = [u8; LENGTH];
+
+/// Marks a line of the best grid as not known yet, any real line being smaller
+const UNKNOWN_LINE: Line = [u8::MAX; LENGTH];
+
+/// Values given in order of first appearance, the next one to give being `next`
+#[derive(Debug, Clone, Copy)]
+struct Labels {
+    digits: [u8; LENGTH + 1],
+    next: u8,
+}
+
+////////////////////////////////////////
+
+/// Smallest grid, read line by line, among every grid equivalent through the sudoku symmetries,
+/// along with the transformation leading to it
+/// Two puzzles differing only by relabeling, line or column swaps, rotations or reflections share
+/// the same canonical form
+pub fn canonical_form(values: &GridValues) -> (GridValues, Transformation) {
+    let mut search = CanonicalSearch {
+        source: values.clone(),
+        columns: [0; LENGTH],
+        transpose: false,
+        best: [UNKNOWN_LINE; LENGTH],
+        best_transformation: Transformation::identity(),
+        lines: [0; LENGTH],
+    };
+
+    let column_maps = band_preserving_maps();
+
+    for transpose in [false, true] {
+        search.transpose = transpose;
+
+        for columns in column_maps.iter() {
+            search.columns = *columns;
+
+            let labels = Labels {
+                digits: [TO_BE_SOLVED; LENGTH + 1],
+                next: 1,
+            };
+
+            search.search_lines(0, [false; LENGTH], labels, false);
+        }
+    }
+
+    let transformation = search.best_transformation;
+
+    (transformation.apply(values), transformation)
+}
+
+/// Stable fingerprint of the canonical form, equivalent puzzles sharing the same one
+/// FNV-1a is used rather than the std hasher, whose output may change between Rust versions
+pub fn fingerprint(values: &GridValues) -> String {
+    let (canonical, _) = canonical_form(values);
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for value in canonical.iter().flatten() {
+        hash ^= *value as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    format!("{hash:016x}")
+}
+
+////////////////////
+
+struct CanonicalSearch {
+    source: GridValues,
+    transpose: bool,
+    columns: [usize; LENGTH],
+    /// Source line used for each line of the grid being built
+    lines: [usize; LENGTH],
+    best: [Line; LENGTH],
+    best_transformation: Transformation,
+}
+
+impl CanonicalSearch {
+    /// Picks the source line of each line in turn, bands staying together, and drops any branch
+    /// already greater than the best grid found
+    fn search_lines(&mut self, depth: usize, used: [bool; LENGTH], labels: Labels, improved: bool) {
+        if depth == LENGTH {
+            if improved {
+                self.best_transformation = self.transformation(&labels);
+            }
+
+            return;
+        }
+
+        for source_line in self.allowed_lines(depth, &used) {
+            let mut new_labels = labels;
+            let line = self.relabeled_line(source_line, &mut new_labels);
+
+            if line > self.best[depth] {
+                continue;
+            }
+
+            let is_smaller = line < self.best[depth];
+
+            if is_smaller {
+                self.best[depth] = line;
+                for next in (depth + 1)..LENGTH {
+                    self.best[next] = UNKNOWN_LINE;
+                }
+            }
+
+            let mut new_used = used;
+            new_used[source_line] = true;
+            self.lines[depth] = source_line;
+
+            self.search_lines(depth + 1, new_used, new_labels, improved || is_smaller);
+        }
+    }
+
+    /// First line of a band can come from any band left, others from the band already started
+    fn allowed_lines(&self, depth: usize, used: &[bool; LENGTH]) -> Vec<usize> {
+        let bands: Vec<usize> = match depth % BAND_SIZE {
+            0 => (0..BAND_SIZE)
+                .filter(|band| (0..BAND_SIZE).all(|offset| !used[band * BAND_SIZE + offset]))
+                .collect(),
+            _ => vec![self.lines[depth - 1] / BAND_SIZE],
+        };
+
+        bands
+            .into_iter()
+            .flat_map(|band| (0..BAND_SIZE).map(move |offset| band * BAND_SIZE + offset))
+            .filter(|line| !used[*line])
+            .collect()
+    }
+
+    fn relabeled_line(&self, source_line: usize, labels: &mut Labels) -> Line {
+        let mut line = [TO_BE_SOLVED; LENGTH];
+
+        for (column, value) in line.iter_mut().enumerate() {
+            let source_value = match self.transpose {
+                false => self.source[source_line][self.columns[column]],
+                true => self.source[self.columns[column]][source_line],
+            };
+
+            if source_value == TO_BE_SOLVED {
+                continue;
+            }
+
+            if labels.digits[source_value as usize] == TO_BE_SOLVED {
+                labels.digits[source_value as usize] = labels.next;
+                labels.next += 1;
+            }
+
+            *value = labels.digits[source_value as usize];
+        }
+
+        line
+    }
+
+    fn transformation(&self, labels: &Labels) -> Transformation {
+        let mut mapping = [TO_BE_SOLVED; LENGTH];
+        let mut next = labels.next;
+
+        // Values absent from the grid get the labels left, in order
+        for (index, label) in mapping.iter_mut().enumerate() {
+            *label = match labels.digits[index + 1] {
+                TO_BE_SOLVED => {
+                    next += 1;
+                    next - 1
+                }
+                digit => digit,
+            };
+        }
+
+        Transformation::from_parts(self.transpose, self.lines, self.columns, mapping)
+    }
+}
+
+////////////////////
+
+/// Every order of the stacks and of the columns within them
+fn band_preserving_maps() -> Vec<[usize; LENGTH]> {
+    let mut maps = vec![];
+
+    for stacks in ORDERS.iter() {
+        for first in ORDERS.iter() {
+            for second in ORDERS.iter() {
+                for third in ORDERS.iter() {
+                    let within = [first, second, third];
+                    let mut map = [0; LENGTH];
+
+                    for (position, stack) in stacks.iter().enumerate() {
+                        for offset in 0..BAND_SIZE {
+                            map[position * BAND_SIZE + offset] =
+                                stack * BAND_SIZE + within[position][offset];
+                        }
+                    }
+
+                    maps.push(map);
+                }
+            }
+        }
+    }
+
+    maps
+}
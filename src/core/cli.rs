@@ -1,4 +1,12 @@
-use std::{env, fmt, path::Path};
+use super::{
+    date::Date, error::SabitaError, file::Format, layout::Layout, rating::Level, solver::Engine,
+};
+
+use std::{
+    env, fmt,
+    io::{self, ErrorKind},
+    path::Path,
+};
 
 ////////////////////////////////////////
 
@@ -6,19 +14,89 @@ use std::{env, fmt, path::Path};
 pub enum ACTION {
     Generate,
     Solve,
+    BatchSolve,
+    Check,
+    Validate,
+    Rate,
+    Canon,
+    Daily,
 
     HelpGenerate,
     HelpSolve,
+    HelpCheck,
+    HelpValidate,
+    HelpRate,
+    HelpCanon,
+    HelpDaily,
     HelpFull,
 
     Version,
 }
 
+/// Sudoku variant the generator can produce
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Variant {
+    Killer,
+    Samurai,
+}
+
+impl Variant {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "killer" => Some(Variant::Killer),
+            "samurai" => Some(Variant::Samurai),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Variant::Killer => write!(f, "killer"),
+            Variant::Samurai => write!(f, "samurai"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ArgParsed {
     pub action: ACTION,
     pub path: Option<String>,
-    pub nb_missing: Option<u8>,
+    /// File written by commands also reading one
+    pub output: Option<String>,
+    pub nb_missing: Option<u16>,
+    pub engine: Engine,
+    pub difficulty: Option<Level>,
+    pub seed: Option<u64>,
+    pub date: Option<Date>,
+    pub layout: Option<Layout>,
+    pub variant: Option<Variant>,
+    pub format: Format,
+    /// Puzzles written in a single collection file, each one different from the others
+    pub count: Option<usize>,
+    pub threads: Option<usize>,
+}
+
+impl ArgParsed {
+    /// Only the action, every other argument left to its default
+    pub fn new(action: ACTION) -> Self {
+        ArgParsed {
+            action,
+            path: None,
+            output: None,
+            nb_missing: None,
+            engine: Engine::default(),
+            difficulty: None,
+            seed: None,
+            date: None,
+            layout: None,
+            variant: None,
+            format: Format::default(),
+            count: None,
+            threads: None,
+        }
+    }
 }
 
 impl fmt::Display for ArgParsed {
@@ -26,9 +104,20 @@ impl fmt::Display for ArgParsed {
         let action = match self.action {
             ACTION::Generate => "generate",
             ACTION::Solve => "solve",
+            ACTION::BatchSolve => "batch solve",
+            ACTION::Check => "check",
+            ACTION::Validate => "validate",
+            ACTION::Rate => "rate",
+            ACTION::Canon => "canon",
+            ACTION::Daily => "daily",
 
             ACTION::HelpGenerate => "help generate",
             ACTION::HelpSolve => "help solve",
+            ACTION::HelpCheck => "help check",
+            ACTION::HelpValidate => "help validate",
+            ACTION::HelpRate => "help rate",
+            ACTION::HelpCanon => "help canon",
+            ACTION::HelpDaily => "help daily",
             ACTION::HelpFull => "help full",
 
             ACTION::Version => "version",
@@ -39,89 +128,392 @@ impl fmt::Display for ArgParsed {
             None => "[none]".to_string(),
         };
 
+        let output = match &self.output {
+            Some(val) => val.clone(),
+            None => "[none]".to_string(),
+        };
+
         let nb_missing = match &self.nb_missing {
             Some(val) => val.to_string(),
             None => "[none]".to_string(),
         };
 
-        write!(f, "action: {action}; path: {path}; nb_missing {nb_missing}")
+        let difficulty = match &self.difficulty {
+            Some(val) => val.to_string(),
+            None => "[none]".to_string(),
+        };
+
+        let seed = match &self.seed {
+            Some(val) => val.to_string(),
+            None => "[none]".to_string(),
+        };
+
+        let date = match &self.date {
+            Some(val) => val.to_string(),
+            None => "[none]".to_string(),
+        };
+
+        let layout = match &self.layout {
+            Some(val) => val.to_string(),
+            None => "[none]".to_string(),
+        };
+
+        let variant = match &self.variant {
+            Some(val) => val.to_string(),
+            None => "[none]".to_string(),
+        };
+
+        let count = match &self.count {
+            Some(val) => val.to_string(),
+            None => "[none]".to_string(),
+        };
+
+        let threads = match &self.threads {
+            Some(val) => val.to_string(),
+            None => "[none]".to_string(),
+        };
+
+        write!(
+            f,
+            "action: {action}; path: {path}; output: {output}; nb_missing {nb_missing}; engine: {}; difficulty: {difficulty}; seed: {seed}; date: {date}; layout: {layout}; variant: {variant}; format: {}; count: {count}; threads: {threads}",
+            self.engine, self.format
+        )
+    }
+}
+
+//////////
+
+/// Positional arguments and `--name value` options of a command
+#[derive(Debug)]
+struct CommandArgs {
+    positionals: Vec<String>,
+    options: Vec<(String, String)>,
+}
+
+impl CommandArgs {
+    /// Checks every option given is part of the allowed ones
+    fn has_only_options(&self, allowed: &[&str]) -> bool {
+        self.options
+            .iter()
+            .all(|(name, _)| allowed.contains(&name.as_str()))
+    }
+
+    fn option(&self, name: &str) -> Option<&String> {
+        self.options
+            .iter()
+            .find(|(option_name, _)| option_name == name)
+            .map(|(_, value)| value)
     }
 }
 
 ////////////////////////////////////////
 
 /// Generates a sudoku
-pub fn parse_args() -> ArgParsed {
+/// Fails when the file to create already exists or the file to read does not
+pub fn parse_args() -> Result<ArgParsed, SabitaError> {
     let args: Vec<String> = env::args().collect();
 
     if args.len() < 2 {
-        return ArgParsed {
-            action: ACTION::HelpFull,
-            path: None,
-            nb_missing: None,
-        };
+        return Ok(ArgParsed::new(ACTION::HelpFull));
     }
 
-    match args[1].as_str() {
+    let parsed = match args[1].as_str() {
         "g" => {
-            if args.len() < 3 {
-                return ArgParsed {
-                    action: ACTION::HelpGenerate,
-                    path: None,
-                    nb_missing: None,
-                };
+            let Some(command_args) = split_options(&args[2..]) else {
+                return Ok(ArgParsed::new(ACTION::HelpGenerate));
+            };
+            let positionals = &command_args.positionals;
+
+            if positionals.is_empty()
+                || positionals.len() > 2
+                || !command_args.has_only_options(&[
+                    "--difficulty",
+                    "--seed",
+                    "--size",
+                    "--variant",
+                    "--format",
+                    "--count",
+                    "--threads",
+                ])
+            {
+                return Ok(ArgParsed::new(ACTION::HelpGenerate));
+            }
+
+            let difficulty = match command_args.option("--difficulty") {
+                Some(name) => match Level::from_name(name) {
+                    Some(level) if level != Level::Diabolical && positionals.len() == 1 => {
+                        Some(level)
+                    }
+                    _ => return Ok(ArgParsed::new(ACTION::HelpGenerate)),
+                },
+                None => None,
+            };
+
+            let seed = match command_args.option("--seed") {
+                Some(value) => match value.parse::<u64>() {
+                    Ok(seed) => Some(seed),
+                    Err(_) => return Ok(ArgParsed::new(ACTION::HelpGenerate)),
+                },
+                None => None,
+            };
+
+            let layout = match command_args.option("--size") {
+                Some(value) => match value.parse::<usize>().ok().and_then(Layout::for_size) {
+                    Some(layout) if difficulty.is_none() => Some(layout),
+                    _ => return Ok(ArgParsed::new(ACTION::HelpGenerate)),
+                },
+                None => None,
+            };
+
+            // A killer decides by itself which values to give, a samurai is made of 9x9 grids
+            let variant = match command_args
+                .option("--variant")
+                .map(|name| Variant::from_name(name))
+            {
+                Some(Some(Variant::Killer)) if difficulty.is_none() && positionals.len() == 1 => {
+                    Some(Variant::Killer)
+                }
+                Some(Some(Variant::Samurai)) if difficulty.is_none() && layout.is_none() => {
+                    Some(Variant::Samurai)
+                }
+                Some(_) => return Ok(ArgParsed::new(ACTION::HelpGenerate)),
+                None => None,
+            };
+
+            // Variants need sections the line format can not hold
+            let format = match command_args
+                .option("--format")
+                .map(|name| Format::from_name(name))
+            {
+                Some(Some(Format::Line)) if variant.is_none() => Format::Line,
+                Some(Some(Format::Grid)) | None => Format::Grid,
+                Some(_) => return Ok(ArgParsed::new(ACTION::HelpGenerate)),
+            };
+
+            // Bulk generation only writes classic puzzles with missing values, one per line of a
+            // collection
+            let count = match command_args.option("--count") {
+                Some(value) => match value.parse::<usize>() {
+                    Ok(count)
+                        if count > 0
+                            && layout.is_none()
+                            && variant.is_none()
+                            && (positionals.len() == 2 || difficulty.is_some()) =>
+                    {
+                        Some(count)
+                    }
+                    _ => return Ok(ArgParsed::new(ACTION::HelpGenerate)),
+                },
+                None => None,
+            };
+
+            if count.is_some()
+                && command_args.option("--format").is_some()
+                && format == Format::Grid
+            {
+                return Err(SabitaError::Unsupported(
+                    "--count writes one puzzle per line, only --format line can be used"
+                        .to_string(),
+                ));
             }
-            let file_path = args[2].clone();
+
+            let threads = match command_args.option("--threads") {
+                Some(value) => match value.parse::<usize>() {
+                    Ok(threads) if threads > 0 && count.is_some() => Some(threads),
+                    _ => return Ok(ArgParsed::new(ACTION::HelpGenerate)),
+                },
+                None => None,
+            };
+
+            let file_path = positionals[0].clone();
 
             if Path::new(&file_path).exists() {
-                panic!("Path '{file_path}' already exists");
+                return Err(path_error(file_path, ErrorKind::AlreadyExists));
             }
 
             let mut nb_missing = None;
 
-            if args.len() == 4 {
-                match (args[3]).parse::<u8>() {
+            if positionals.len() == 2 {
+                match (positionals[1]).parse::<u16>() {
                     Ok(number) => nb_missing = Some(number),
-                    Err(err) => panic!("Wrong number of box to remove: {}", err),
+                    Err(err) => {
+                        return Err(SabitaError::Parse(format!(
+                            "wrong number of box to remove: {err}"
+                        )))
+                    }
                 };
             }
 
             ArgParsed {
-                action: ACTION::Generate,
                 path: Some(file_path),
                 nb_missing,
+                difficulty,
+                seed,
+                layout,
+                variant,
+                format,
+                count,
+                threads,
+                ..ArgParsed::new(ACTION::Generate)
             }
         }
         "s" => {
-            if args.len() != 3 {
-                return ArgParsed {
-                    action: ACTION::HelpSolve,
-                    path: None,
-                    nb_missing: None,
-                };
+            let Some(command_args) = split_options(&args[2..]) else {
+                return Ok(ArgParsed::new(ACTION::HelpSolve));
+            };
+
+            // Solutions of a whole collection go to a file, in the line format
+            if let Some(file_path) = command_args.option("--batch") {
+                if command_args.positionals.len() != 1
+                    || !command_args.has_only_options(&["--batch"])
+                {
+                    return Ok(ArgParsed::new(ACTION::HelpSolve));
+                }
+
+                let output = command_args.positionals[0].clone();
+
+                if !Path::new(file_path).exists() {
+                    return Err(path_error(file_path.clone(), ErrorKind::NotFound));
+                }
+
+                if Path::new(&output).exists() {
+                    return Err(path_error(output, ErrorKind::AlreadyExists));
+                }
+
+                return Ok(ArgParsed {
+                    path: Some(file_path.clone()),
+                    output: Some(output),
+                    ..ArgParsed::new(ACTION::BatchSolve)
+                });
+            }
+
+            if command_args.positionals.len() != 1
+                || !command_args.has_only_options(&["--engine", "--format"])
+            {
+                return Ok(ArgParsed::new(ACTION::HelpSolve));
             }
 
-            let file_path = args[2].clone();
+            let engine = match command_args.option("--engine") {
+                Some(name) => match Engine::from_name(name) {
+                    Some(engine) => engine,
+                    None => return Ok(ArgParsed::new(ACTION::HelpSolve)),
+                },
+                None => Engine::default(),
+            };
+
+            let format = match command_args.option("--format") {
+                Some(name) => match Format::from_name(name) {
+                    Some(format) => format,
+                    None => return Ok(ArgParsed::new(ACTION::HelpSolve)),
+                },
+                None => Format::default(),
+            };
+
+            let file_path = command_args.positionals[0].clone();
 
             if !Path::new(&file_path).exists() {
-                panic!("Path '{file_path}' doesn't exists");
+                return Err(path_error(file_path, ErrorKind::NotFound));
             }
 
             ArgParsed {
-                action: ACTION::Solve,
                 path: Some(file_path),
-                nb_missing: None,
+                engine,
+                format,
+                ..ArgParsed::new(ACTION::Solve)
             }
         }
-        "-v" | "--version" => ArgParsed {
-            action: ACTION::Version,
-            path: None,
-            nb_missing: None,
-        },
-        _ => ArgParsed {
-            action: ACTION::HelpFull,
-            path: None,
-            nb_missing: None,
-        },
+        "check" => return parse_existing_file(&args, ACTION::Check, ACTION::HelpCheck),
+        "validate" => return parse_existing_file(&args, ACTION::Validate, ACTION::HelpValidate),
+        "rate" => return parse_existing_file(&args, ACTION::Rate, ACTION::HelpRate),
+        "canon" => return parse_existing_file(&args, ACTION::Canon, ACTION::HelpCanon),
+        "daily" => {
+            let Some(command_args) = split_options(&args[2..]) else {
+                return Ok(ArgParsed::new(ACTION::HelpDaily));
+            };
+
+            if !command_args.positionals.is_empty()
+                || !command_args.has_only_options(&["--date", "--difficulty"])
+            {
+                return Ok(ArgParsed::new(ACTION::HelpDaily));
+            }
+
+            let date = match command_args.option("--date") {
+                Some(text) => match Date::parse(text) {
+                    Ok(date) => Some(date),
+                    Err(_) => return Ok(ArgParsed::new(ACTION::HelpDaily)),
+                },
+                None => None,
+            };
+
+            let difficulty = match command_args.option("--difficulty") {
+                Some(name) => match Level::from_name(name) {
+                    Some(level) if level != Level::Diabolical => Some(level),
+                    _ => return Ok(ArgParsed::new(ACTION::HelpDaily)),
+                },
+                None => None,
+            };
+
+            ArgParsed {
+                date,
+                difficulty,
+                ..ArgParsed::new(ACTION::Daily)
+            }
+        }
+        "-v" | "--version" => ArgParsed::new(ACTION::Version),
+        _ => ArgParsed::new(ACTION::HelpFull),
+    };
+
+    Ok(parsed)
+}
+
+/// Commands only taking the path of an existing file
+fn parse_existing_file(
+    args: &[String],
+    action: ACTION,
+    help: ACTION,
+) -> Result<ArgParsed, SabitaError> {
+    if args.len() != 3 {
+        return Ok(ArgParsed::new(help));
     }
+
+    let file_path = args[2].clone();
+
+    if !Path::new(&file_path).exists() {
+        return Err(path_error(file_path, ErrorKind::NotFound));
+    }
+
+    Ok(ArgParsed {
+        path: Some(file_path),
+        ..ArgParsed::new(action)
+    })
+}
+
+fn path_error(path: String, kind: ErrorKind) -> SabitaError {
+    SabitaError::Io {
+        path,
+        error: io::Error::from(kind),
+    }
+}
+
+/// Separates `--name value` options from positional arguments
+/// Returns None if an option is missing its value
+fn split_options(args: &[String]) -> Option<CommandArgs> {
+    let mut positionals = vec![];
+    let mut options = vec![];
+
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        if arg.starts_with("--") {
+            let value = iter.next()?;
+            options.push((arg.clone(), value.clone()));
+        } else {
+            positionals.push(arg.clone());
+        }
+    }
+
+    Some(CommandArgs {
+        positionals,
+        options,
+    })
 }
@@ -1,6 +1,9 @@
 pub const LENGTH_DIMENSION: u8 = 9;
 pub const MAX_NB_VALUES: u8 = LENGTH_DIMENSION.pow(2);
 
+/// Largest grid handled, values above 9 being written as letters
+pub const MAX_LENGTH_DIMENSION: u8 = 25;
+
 pub const TO_BE_SOLVED: u8 = 0;
 
 /// The minimum number of element to be present in order to ensure a single solution
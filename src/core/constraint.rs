@@ -0,0 +1,486 @@
+use super::{
+    board::{all_candidates, value_to_mask, Board, Candidates},
+    constants::TO_BE_SOLVED,
+    edge_constraint::{EdgeConstraint, EdgeFamily},
+    file::{parse_rows, rows_to_lines, Section},
+    killer::{Killer, KILLER_SECTION},
+    layout::Layout,
+    line_constraint::{LineConstraint, LineKind},
+};
+
+use std::{fmt, sync::Arc};
+
+////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct InvalidConstraint {
+    reason: String,
+}
+
+impl InvalidConstraint {
+    pub(crate) fn new(reason: String) -> Self {
+        InvalidConstraint { reason }
+    }
+}
+
+impl fmt::Display for InvalidConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid constraint: {}", self.reason)
+    }
+}
+
+//////////
+
+const DIAGONAL_SECTION: &str = "diagonal";
+const ANTI_KNIGHT_SECTION: &str = "anti-knight";
+const ANTI_KING_SECTION: &str = "anti-king";
+const NON_CONSECUTIVE_SECTION: &str = "non-consecutive";
+const EXTRA_REGIONS_SECTION: &str = "extra-regions";
+const PARITY_SECTION: &str = "parity";
+
+const KNIGHT_MOVES: [(isize, isize); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+
+const KING_MOVES: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+const ORTHOGONAL_MOVES: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+////////////////////////////////////////
+
+/// Rule of a sudoku variant, checked on top of the lines, columns and regions
+/// Boards consult their constraints each time they list the candidates of a box, so every solver
+/// working on boards, along with the generator and the validation, follows them
+pub trait Constraint: fmt::Debug + Send + Sync {
+    /// Name of the file section declaring the constraint
+    fn name(&self) -> &str;
+
+    /// Keeps the candidates of the box allowed by the values already on the board
+    fn prune(&self, board: &Board, index: usize, candidates: Candidates) -> Candidates;
+
+    /// Checks the value can go in the box, given the values of the other boxes
+    fn allows(&self, board: &Board, index: usize, value: u8) -> bool {
+        self.prune(board, index, value_to_mask(value)) != 0
+    }
+
+    /// Filled boxes keeping the value out of the box, each one breaking the constraint along with
+    /// it, the box itself excluded
+    fn conflicting_boxes(&self, board: &Board, index: usize, value: u8) -> Vec<usize> {
+        pairwise_conflicts(self, board, index, value)
+    }
+
+    /// File section declaring the constraint
+    fn to_section(&self) -> Section {
+        Section {
+            name: self.name().to_string(),
+            lines: vec![],
+        }
+    }
+}
+
+////////////////////
+
+/// Sudoku-X: each value appears once on each of the two main diagonals
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Diagonal;
+
+impl Constraint for Diagonal {
+    fn name(&self) -> &str {
+        DIAGONAL_SECTION
+    }
+
+    fn prune(&self, board: &Board, index: usize, candidates: Candidates) -> Candidates {
+        let size = board.layout().size();
+        let (line, column) = (index / size, index % size);
+
+        let mut seen = 0;
+
+        for position in 0..size {
+            if line == column && position != line {
+                seen |= mask_of(board, position * size + position);
+            }
+            if line + column == size - 1 && position != line {
+                seen |= mask_of(board, position * size + size - 1 - position);
+            }
+        }
+
+        candidates & !seen
+    }
+}
+
+//////////
+
+/// Two boxes a chess knight's move apart never hold the same value
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AntiKnight;
+
+impl Constraint for AntiKnight {
+    fn name(&self) -> &str {
+        ANTI_KNIGHT_SECTION
+    }
+
+    fn prune(&self, board: &Board, index: usize, candidates: Candidates) -> Candidates {
+        candidates & !seen_at(board, index, &KNIGHT_MOVES)
+    }
+}
+
+//////////
+
+/// Two boxes touching, even by a corner, never hold the same value
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AntiKing;
+
+impl Constraint for AntiKing {
+    fn name(&self) -> &str {
+        ANTI_KING_SECTION
+    }
+
+    fn prune(&self, board: &Board, index: usize, candidates: Candidates) -> Candidates {
+        candidates & !seen_at(board, index, &KING_MOVES)
+    }
+}
+
+//////////
+
+/// Two boxes sharing a side never hold consecutive values
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NonConsecutive;
+
+impl Constraint for NonConsecutive {
+    fn name(&self) -> &str {
+        NON_CONSECUTIVE_SECTION
+    }
+
+    fn prune(&self, board: &Board, index: usize, candidates: Candidates) -> Candidates {
+        let seen = seen_at(board, index, &ORTHOGONAL_MOVES);
+        let consecutive = (seen << 1) | (seen >> 1);
+
+        candidates & !consecutive
+    }
+}
+
+//////////
+
+/// Additional groups of boxes holding each value at most once, such as the four inner regions of
+/// a windoku (or hyper sudoku)
+#[derive(Debug, Clone)]
+pub struct ExtraRegions {
+    size: usize,
+    /// Flat indices of the boxes of each extra region
+    regions: Vec<Vec<usize>>,
+}
+
+impl ExtraRegions {
+    /// Regions given by rows of ids, 0 for boxes out of any region and 1 and above for the others
+    pub fn from_rows(rows: &[Vec<u8>]) -> Result<Self, InvalidConstraint> {
+        let size = rows.len();
+
+        if let Some(line) = rows.iter().position(|row| row.len() != size) {
+            return Err(InvalidConstraint {
+                reason: format!("line {line} of the extra regions is not {size} boxes long"),
+            });
+        }
+
+        let mut regions: Vec<Vec<usize>> = vec![];
+
+        for (index, id) in rows.iter().flatten().enumerate() {
+            if *id == 0 {
+                continue;
+            }
+
+            let position = *id as usize - 1;
+
+            if regions.len() <= position {
+                regions.resize(position + 1, vec![]);
+            }
+
+            regions[position].push(index);
+        }
+
+        if let Some(region) = regions.iter().position(|boxes| boxes.len() > size) {
+            return Err(InvalidConstraint {
+                reason: format!("extra region {} holds more than {size} boxes", region + 1),
+            });
+        }
+
+        Ok(ExtraRegions { size, regions })
+    }
+
+    /// The four inner 3x3 regions of a 9x9 windoku
+    pub fn windoku() -> Self {
+        let layout = Layout::standard();
+        let size = layout.size();
+
+        let regions = [(1, 1), (1, 5), (5, 1), (5, 5)]
+            .iter()
+            .map(|(top, left)| {
+                let mut boxes = vec![];
+
+                for line in *top..top + 3 {
+                    for column in *left..left + 3 {
+                        boxes.push(line * size + column);
+                    }
+                }
+
+                boxes
+            })
+            .collect();
+
+        ExtraRegions { size, regions }
+    }
+}
+
+impl Constraint for ExtraRegions {
+    fn name(&self) -> &str {
+        EXTRA_REGIONS_SECTION
+    }
+
+    fn prune(&self, board: &Board, index: usize, candidates: Candidates) -> Candidates {
+        let mut seen = 0;
+
+        for region in self.regions.iter().filter(|boxes| boxes.contains(&index)) {
+            for other in region.iter().filter(|other| **other != index) {
+                seen |= mask_of(board, *other);
+            }
+        }
+
+        candidates & !seen
+    }
+
+    fn to_section(&self) -> Section {
+        let mut rows = vec![vec![0; self.size]; self.size];
+
+        for (position, region) in self.regions.iter().enumerate() {
+            for index in region {
+                rows[index / self.size][index % self.size] = position as u8 + 1;
+            }
+        }
+
+        Section {
+            name: self.name().to_string(),
+            lines: rows_to_lines(&rows),
+        }
+    }
+}
+
+//////////
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    Odd,
+    Even,
+}
+
+/// Boxes shaded to only hold odd or even values
+#[derive(Debug, Clone)]
+pub struct ParityCells {
+    size: usize,
+    cells: Vec<(usize, Parity)>,
+}
+
+impl ParityCells {
+    /// Parity of boxes of a grid of the given size, given by their flat index
+    pub fn new(size: usize, cells: Vec<(usize, Parity)>) -> Self {
+        ParityCells { size, cells }
+    }
+
+    /// Reads lines such as `e.o......`, `e` marking even boxes, `o` odd ones and `.` free ones,
+    /// commas and spaces being ignored
+    pub fn from_lines(lines: &[String]) -> Result<Self, InvalidConstraint> {
+        let size = lines.len();
+        let mut cells = vec![];
+
+        for (line, text) in lines.iter().enumerate() {
+            let marks: Vec<char> = text.chars().filter(|c| *c != ',' && *c != ' ').collect();
+
+            if marks.len() != size {
+                return Err(InvalidConstraint {
+                    reason: format!("line {line} of the parity is not {size} boxes long"),
+                });
+            }
+
+            for (column, mark) in marks.iter().enumerate() {
+                let parity = match mark.to_ascii_lowercase() {
+                    'e' => Parity::Even,
+                    'o' => Parity::Odd,
+                    '.' | '0' => continue,
+                    _ => {
+                        return Err(InvalidConstraint {
+                            reason: format!("unknown parity '{mark}' at [{line}:{column}]"),
+                        })
+                    }
+                };
+
+                cells.push((line * size + column, parity));
+            }
+        }
+
+        Ok(ParityCells { size, cells })
+    }
+}
+
+impl Constraint for ParityCells {
+    fn name(&self) -> &str {
+        PARITY_SECTION
+    }
+
+    fn prune(&self, board: &Board, index: usize, candidates: Candidates) -> Candidates {
+        let odd_values = all_candidates(board.layout().size()) & ODD_MASK;
+
+        match self.cells.iter().find(|(cell, _)| *cell == index) {
+            Some((_, Parity::Odd)) => candidates & odd_values,
+            Some((_, Parity::Even)) => candidates & !odd_values,
+            None => candidates,
+        }
+    }
+
+    fn to_section(&self) -> Section {
+        let mut rows = vec![vec!['.'; self.size]; self.size];
+
+        for (index, parity) in self.cells.iter() {
+            rows[index / self.size][index % self.size] = match parity {
+                Parity::Odd => 'o',
+                Parity::Even => 'e',
+            };
+        }
+
+        Section {
+            name: self.name().to_string(),
+            lines: rows.iter().map(|row| row.iter().collect()).collect(),
+        }
+    }
+}
+
+/// Bits of the values 1, 3, 5...
+const ODD_MASK: Candidates = 0x5555_5555;
+
+////////////////////////////////////////
+
+/// Constraint declared by a file section, None if the section is not one of a constraint
+pub fn from_section(
+    section: &Section,
+    layout: &Layout,
+) -> Option<Result<Arc<dyn Constraint>, InvalidConstraint>> {
+    let constraint: Result<Arc<dyn Constraint>, InvalidConstraint> = match section.name.as_str() {
+        DIAGONAL_SECTION => Ok(Arc::new(Diagonal)),
+        ANTI_KNIGHT_SECTION => Ok(Arc::new(AntiKnight)),
+        ANTI_KING_SECTION => Ok(Arc::new(AntiKing)),
+        NON_CONSECUTIVE_SECTION => Ok(Arc::new(NonConsecutive)),
+        EXTRA_REGIONS_SECTION => expect_grid_lines(section, layout)
+            .and_then(|_| {
+                parse_rows(section).map_err(|err| InvalidConstraint::new(err.to_string()))
+            })
+            .and_then(|rows| ExtraRegions::from_rows(&rows))
+            .map(|regions| Arc::new(regions) as Arc<dyn Constraint>),
+        PARITY_SECTION => expect_grid_lines(section, layout)
+            .and_then(|_| ParityCells::from_lines(&section.lines))
+            .map(|cells| Arc::new(cells) as Arc<dyn Constraint>),
+        KILLER_SECTION => Killer::from_section(section, layout)
+            .map(|killer| Arc::new(killer) as Arc<dyn Constraint>),
+        name if LineKind::from_name(name).is_some() => {
+            LineConstraint::from_section(section, layout)
+                .map(|lines| Arc::new(lines) as Arc<dyn Constraint>)
+        }
+        name if EdgeFamily::from_name(name).is_some() => {
+            EdgeConstraint::from_section(section, layout)
+                .map(|edges| Arc::new(edges) as Arc<dyn Constraint>)
+        }
+        _ => return None,
+    };
+
+    Some(constraint)
+}
+
+/// Checks a section drawn over the grid has a line per line of the grid
+fn expect_grid_lines(section: &Section, layout: &Layout) -> Result<(), InvalidConstraint> {
+    match section.lines.len() {
+        nb_lines if nb_lines == layout.size() => Ok(()),
+        nb_lines => Err(InvalidConstraint {
+            reason: format!(
+                "section [{}] has {nb_lines} lines for a grid of {}",
+                section.name,
+                layout.size()
+            ),
+        }),
+    }
+}
+
+/// Flat index of a box written `line:column` in a section, None if it is not part of the grid
+/// Filled boxes which, alone on the board with the box, keep the value out of it
+/// None when the box breaks the constraint on its own
+pub(crate) fn pairwise_conflicts<C: Constraint + ?Sized>(
+    constraint: &C,
+    board: &Board,
+    index: usize,
+    value: u8,
+) -> Vec<usize> {
+    let mut alone = Board::empty(board.layout());
+    alone.place(index, value);
+
+    if !constraint.allows(&alone, index, value) {
+        return vec![];
+    }
+
+    (0..board.layout().nb_boxes())
+        .filter(|other| *other != index && board.get(*other) != TO_BE_SOLVED)
+        .filter(|other| {
+            let mut pair = alone.clone();
+            pair.place(*other, board.get(*other));
+
+            !constraint.allows(&pair, index, value)
+        })
+        .collect()
+}
+
+pub(crate) fn parse_cell(word: &str, layout: &Layout) -> Option<usize> {
+    let (line, column) = word.split_once(':')?;
+    let line: usize = line.parse().ok()?;
+    let column: usize = column.parse().ok()?;
+
+    (line < layout.size() && column < layout.size()).then_some(line * layout.size() + column)
+}
+
+/// Box given by its flat index, written `line:column`
+pub(crate) fn cell_to_string(cell: usize, size: usize) -> String {
+    format!("{}:{}", cell / size, cell % size)
+}
+
+////////////////////
+
+/// Value of the box as a mask, 0 when the box is empty
+fn mask_of(board: &Board, index: usize) -> Candidates {
+    match board.get(index) {
+        TO_BE_SOLVED => 0,
+        value => value_to_mask(value),
+    }
+}
+
+/// Values of the boxes found at the given moves from a box
+fn seen_at(board: &Board, index: usize, moves: &[(isize, isize)]) -> Candidates {
+    let size = board.layout().size() as isize;
+    let (line, column) = (index as isize / size, index as isize % size);
+
+    moves
+        .iter()
+        .map(|(line_move, column_move)| (line + line_move, column + column_move))
+        .filter(|(line, column)| (0..size).contains(line) && (0..size).contains(column))
+        .fold(0, |seen, (line, column)| {
+            seen | mask_of(board, (line * size + column) as usize)
+        })
+}
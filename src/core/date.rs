@@ -0,0 +1,139 @@
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct InvalidDate {
+    text: String,
+}
+
+impl fmt::Display for InvalidDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "'{}' is not a valid YYYY-MM-DD date", self.text)
+    }
+}
+
+//////////
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+////////////////////////////////////////
+
+/// Calendar day, without any time zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+////////////////////
+
+impl Date {
+    //////////
+    // Constructors
+
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, InvalidDate> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(InvalidDate {
+                text: format!("{year:04}-{month:02}-{day:02}"),
+            });
+        }
+
+        Ok(Date { year, month, day })
+    }
+
+    /// Reads a `YYYY-MM-DD` date
+    pub fn parse(text: &str) -> Result<Self, InvalidDate> {
+        let invalid = || InvalidDate {
+            text: text.to_string(),
+        };
+
+        let parts: Vec<&str> = text.split('-').collect();
+
+        if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2 {
+            return Err(invalid());
+        }
+
+        let year = parts[0].parse::<u16>().map_err(|_| invalid())?;
+        let month = parts[1].parse::<u8>().map_err(|_| invalid())?;
+        let day = parts[2].parse::<u8>().map_err(|_| invalid())?;
+
+        Date::new(year, month, day).map_err(|_| invalid())
+    }
+
+    /// Current day in UTC, so that every machine agrees whatever its time zone
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+
+        Date::from_days_since_epoch(seconds / SECONDS_PER_DAY)
+    }
+
+    /// Converts a number of days since 1970-01-01 to a calendar day
+    /// @see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    fn from_days_since_epoch(days: u64) -> Self {
+        let shifted = days + 719_468;
+        let era = shifted / 146_097;
+        let day_of_era = shifted % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+        Date {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    //////////
+    // Accessors
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+////////////////////
+
+fn is_leap_year(year: u16) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
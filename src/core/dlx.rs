@@ -0,0 +1,331 @@
+use super::{
+    board::Board,
+    constants::TO_BE_SOLVED,
+    grid::GridValues,
+    layout::Layout,
+    solver::{Backtracking, NoSudokuSolutionFound, Solver},
+};
+
+////////////////////////////////////////
+
+/// Constraints of a box: being filled, then holding its value once in its line, column and region
+const NB_CONSTRAINTS: usize = 4;
+
+const ROOT: usize = 0;
+
+////////////////////////////////////////
+
+/// Models the sudoku as an exact cover problem and solves it with Knuth's Dancing Links, which
+/// keeps hard puzzles with few clues in a predictable time
+/// Boards with variant constraints are handed to the backtracking engine, as the constraints only
+/// know how to prune candidates and have no exact cover columns
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DancingLinks;
+
+impl Solver for DancingLinks {
+    fn solve_board(&self, board: &Board) -> Result<GridValues, NoSudokuSolutionFound> {
+        if !board.constraints().is_empty() {
+            return Backtracking.solve_board(board);
+        }
+
+        let grid_values = board.get_values();
+        let mut links = Links::new(board.layout());
+
+        if !links.select_clues(&grid_values) {
+            return Err(NoSudokuSolutionFound);
+        }
+
+        let mut nb_found = 0;
+        let mut partial = vec![];
+        let mut first_solution = None;
+
+        links.search(1, &mut nb_found, &mut partial, &mut first_solution);
+
+        match first_solution {
+            Some(rows) => {
+                let mut solved = grid_values.clone();
+
+                for row in rows {
+                    let (line, column, value) = links.decode_row(row);
+                    solved[line][column] = value;
+                }
+
+                Ok(solved)
+            }
+            None => Err(NoSudokuSolutionFound),
+        }
+    }
+
+    fn count_board_solutions(&self, board: &Board, limit: usize) -> usize {
+        if !board.constraints().is_empty() {
+            return Backtracking.count_board_solutions(board, limit);
+        }
+
+        let mut links = Links::new(board.layout());
+
+        if limit == 0 || !links.select_clues(&board.get_values()) {
+            return 0;
+        }
+
+        let mut nb_found = 0;
+
+        links.search(limit, &mut nb_found, &mut vec![], &mut None);
+
+        nb_found
+    }
+}
+
+////////////////////
+
+/// Toroidal doubly linked lists stored in flat vectors, node 0 being the root and nodes
+/// 1..=nb_columns the column headers
+struct Links {
+    layout: Layout,
+    nb_columns: usize,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    row: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl Links {
+    fn new(layout: &Layout) -> Self {
+        let nb_columns = layout.nb_boxes() * NB_CONSTRAINTS;
+        let nb_rows = layout.nb_boxes() * layout.size();
+
+        let nb_headers = nb_columns + 1;
+        let capacity = nb_headers + nb_rows * NB_CONSTRAINTS;
+
+        let mut links = Links {
+            layout: layout.clone(),
+            nb_columns,
+            left: Vec::with_capacity(capacity),
+            right: Vec::with_capacity(capacity),
+            up: Vec::with_capacity(capacity),
+            down: Vec::with_capacity(capacity),
+            column: Vec::with_capacity(capacity),
+            row: Vec::with_capacity(capacity),
+            size: vec![0; nb_headers],
+        };
+
+        for header in 0..nb_headers {
+            links
+                .left
+                .push(if header == 0 { nb_columns } else { header - 1 });
+            links
+                .right
+                .push(if header == nb_columns { 0 } else { header + 1 });
+            links.up.push(header);
+            links.down.push(header);
+            links.column.push(header);
+            links.row.push(usize::MAX);
+        }
+
+        for row in 0..nb_rows {
+            links.add_row(row);
+        }
+
+        links
+    }
+
+    fn add_row(&mut self, row: usize) {
+        let first = self.left.len();
+        let columns = self.row_columns(row);
+
+        for (offset, header) in columns.iter().enumerate() {
+            let node = first + offset;
+            let last_in_column = self.up[*header];
+
+            self.left.push(if offset == 0 {
+                first + NB_CONSTRAINTS - 1
+            } else {
+                node - 1
+            });
+            self.right.push(if offset == NB_CONSTRAINTS - 1 {
+                first
+            } else {
+                node + 1
+            });
+            self.up.push(last_in_column);
+            self.down.push(*header);
+            self.column.push(*header);
+            self.row.push(row);
+
+            self.down[last_in_column] = node;
+            self.up[*header] = node;
+            self.size[*header] += 1;
+        }
+    }
+
+    /// Puts the given values in the solution, returns false if two of them conflict
+    fn select_clues(&mut self, grid_values: &GridValues) -> bool {
+        let mut covered = vec![false; self.nb_columns + 1];
+
+        for (line, row_values) in grid_values.iter().enumerate() {
+            for (column, value) in row_values.iter().enumerate() {
+                if *value == TO_BE_SOLVED {
+                    continue;
+                }
+
+                let row = self.encode_row(line, column, *value);
+
+                for header in self.row_columns(row) {
+                    if covered[header] {
+                        return false;
+                    }
+
+                    covered[header] = true;
+                    self.cover(header);
+                }
+            }
+        }
+
+        true
+    }
+
+    fn cover(&mut self, header: usize) {
+        self.right[self.left[header]] = self.right[header];
+        self.left[self.right[header]] = self.left[header];
+
+        let mut row_node = self.down[header];
+        while row_node != header {
+            let mut node = self.right[row_node];
+            while node != row_node {
+                self.down[self.up[node]] = self.down[node];
+                self.up[self.down[node]] = self.up[node];
+                self.size[self.column[node]] -= 1;
+
+                node = self.right[node];
+            }
+
+            row_node = self.down[row_node];
+        }
+    }
+
+    fn uncover(&mut self, header: usize) {
+        let mut row_node = self.up[header];
+        while row_node != header {
+            let mut node = self.left[row_node];
+            while node != row_node {
+                self.size[self.column[node]] += 1;
+                self.down[self.up[node]] = node;
+                self.up[self.down[node]] = node;
+
+                node = self.left[node];
+            }
+
+            row_node = self.up[row_node];
+        }
+
+        self.right[self.left[header]] = header;
+        self.left[self.right[header]] = header;
+    }
+
+    /// Column with the fewest rows left, None when every column is covered
+    fn smallest_column(&self) -> Option<usize> {
+        let mut best = None;
+        let mut header = self.right[ROOT];
+
+        while header != ROOT {
+            match best {
+                Some(best_header) if self.size[header] >= self.size[best_header] => {}
+                _ => best = Some(header),
+            }
+
+            header = self.right[header];
+        }
+
+        best
+    }
+
+    /// Algorithm X, returns true once `limit` solutions have been found
+    fn search(
+        &mut self,
+        limit: usize,
+        nb_found: &mut usize,
+        partial: &mut Vec<usize>,
+        first_solution: &mut Option<Vec<usize>>,
+    ) -> bool {
+        let Some(header) = self.smallest_column() else {
+            *nb_found += 1;
+
+            if first_solution.is_none() {
+                *first_solution = Some(partial.clone());
+            }
+
+            return *nb_found >= limit;
+        };
+
+        if self.size[header] == 0 {
+            return false;
+        }
+
+        self.cover(header);
+
+        let mut row_node = self.down[header];
+        while row_node != header {
+            partial.push(self.row[row_node]);
+
+            let mut node = self.right[row_node];
+            while node != row_node {
+                self.cover(self.column[node]);
+                node = self.right[node];
+            }
+
+            let is_done = self.search(limit, nb_found, partial, first_solution);
+
+            let mut node = self.left[row_node];
+            while node != row_node {
+                self.uncover(self.column[node]);
+                node = self.left[node];
+            }
+
+            partial.pop();
+
+            if is_done {
+                self.uncover(header);
+                return true;
+            }
+
+            row_node = self.down[row_node];
+        }
+
+        self.uncover(header);
+
+        false
+    }
+
+    fn encode_row(&self, line: usize, column: usize, value: u8) -> usize {
+        let size = self.layout.size();
+
+        (line * size + column) * size + (value as usize - 1)
+    }
+
+    fn decode_row(&self, row: usize) -> (usize, usize, u8) {
+        let size = self.layout.size();
+        let index = row / size;
+        let value = (row % size) as u8 + 1;
+
+        (index / size, index % size, value)
+    }
+
+    /// Column headers satisfied by placing a value in a box
+    fn row_columns(&self, row: usize) -> [usize; NB_CONSTRAINTS] {
+        let size = self.layout.size();
+        let nb_boxes = self.layout.nb_boxes();
+
+        let (line, column, value) = self.decode_row(row);
+        let region = self.layout.region_of(line, column) as usize;
+        let value_index = value as usize - 1;
+
+        [
+            1 + line * size + column,
+            1 + nb_boxes + line * size + value_index,
+            1 + nb_boxes * 2 + column * size + value_index,
+            1 + nb_boxes * 3 + region * size + value_index,
+        ]
+    }
+}
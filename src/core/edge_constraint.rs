@@ -0,0 +1,395 @@
+use super::{
+    board::{value_to_mask, Board, Candidates},
+    constants::TO_BE_SOLVED,
+    constraint::{cell_to_string, parse_cell, Constraint, InvalidConstraint},
+    error::SabitaError,
+    file::Section,
+    grid::{check_dimensions, GridValues},
+    layout::Layout,
+};
+
+////////////////////////////////////////
+
+const KROPKI_SECTION: &str = "kropki";
+const XV_SECTION: &str = "xv";
+
+/// Line of a section stating every possible mark is given
+const NEGATIVE_LINE: &str = "negative";
+
+////////////////////////////////////////
+
+/// Annotation on the edge between two boxes sharing a side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeMark {
+    /// Kropki white dot: consecutive values
+    White,
+    /// Kropki black dot: one value is twice the other
+    Black,
+    /// Values adding up to 10
+    X,
+    /// Values adding up to 5
+    V,
+}
+
+impl EdgeMark {
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol.to_ascii_lowercase().as_str() {
+            "w" => Some(EdgeMark::White),
+            "b" => Some(EdgeMark::Black),
+            "x" => Some(EdgeMark::X),
+            "v" => Some(EdgeMark::V),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            EdgeMark::White => "w",
+            EdgeMark::Black => "b",
+            EdgeMark::X => "x",
+            EdgeMark::V => "v",
+        }
+    }
+
+    /// Checks two values fit the mark
+    pub fn holds(&self, value: u8, other: u8) -> bool {
+        match self {
+            EdgeMark::White => value.abs_diff(other) == 1,
+            EdgeMark::Black => value == 2 * other || other == 2 * value,
+            EdgeMark::X => value + other == 10,
+            EdgeMark::V => value + other == 5,
+        }
+    }
+}
+
+//////////
+
+/// Marks sharing a section, and a negative constraint when every possible one is given
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeFamily {
+    Kropki,
+    XV,
+}
+
+impl EdgeFamily {
+    /// Family declared by a file section of that name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            KROPKI_SECTION => Some(EdgeFamily::Kropki),
+            XV_SECTION => Some(EdgeFamily::XV),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EdgeFamily::Kropki => KROPKI_SECTION,
+            EdgeFamily::XV => XV_SECTION,
+        }
+    }
+
+    pub fn marks(&self) -> &'static [EdgeMark] {
+        match self {
+            EdgeFamily::Kropki => &[EdgeMark::White, EdgeMark::Black],
+            EdgeFamily::XV => &[EdgeMark::X, EdgeMark::V],
+        }
+    }
+}
+
+//////////
+
+/// Marks of one family on the edges between boxes sharing a side
+/// With the negative constraint, two neighbours without a mark between them fit none of the marks
+/// of the family
+#[derive(Debug, Clone)]
+pub struct EdgeConstraint {
+    family: EdgeFamily,
+    size: usize,
+    /// Mark between each box and its right neighbour
+    right: Vec<Option<EdgeMark>>,
+    /// Mark between each box and the box below it
+    down: Vec<Option<EdgeMark>>,
+    negative: bool,
+}
+
+////////////////////
+
+impl EdgeConstraint {
+    //////////
+    // Constructors
+
+    /// Marks given by the flat indices of the two boxes of each edge
+    pub fn new(
+        family: EdgeFamily,
+        layout: &Layout,
+        marks: &[(usize, usize, EdgeMark)],
+        negative: bool,
+    ) -> Result<Self, InvalidConstraint> {
+        let size = layout.size();
+
+        let mut edges = EdgeConstraint {
+            family,
+            size,
+            right: vec![None; layout.nb_boxes()],
+            down: vec![None; layout.nb_boxes()],
+            negative,
+        };
+
+        for (cell, other, mark) in marks.iter().copied() {
+            let invalid = |reason: &str| {
+                Err(InvalidConstraint::new(format!(
+                    "{} mark between {} and {} {reason}",
+                    mark.symbol(),
+                    cell_to_string(cell, size),
+                    cell_to_string(other, size)
+                )))
+            };
+
+            if !family.marks().contains(&mark) {
+                return invalid(&format!("is not a {} mark", family.name()));
+            }
+
+            let Some(edge) = edges.edge_mut(cell, other) else {
+                return invalid("is not between neighbours");
+            };
+
+            if edge.is_some() {
+                return invalid("is given twice");
+            }
+
+            *edge = Some(mark);
+        }
+
+        Ok(edges)
+    }
+
+    /// Every mark of the family fitting a full grid, the first one of the family being kept when
+    /// two of them fit, along with the negative constraint
+    /// Fails when the values do not fill the layout
+    pub fn from_solution(
+        family: EdgeFamily,
+        layout: &Layout,
+        values: &GridValues,
+    ) -> Result<Self, SabitaError> {
+        check_dimensions(values, layout)?;
+
+        let size = layout.size();
+        let mut marks = vec![];
+
+        for (cell, other) in neighbour_pairs(size) {
+            let value = values[cell / size][cell % size];
+            let other_value = values[other / size][other % size];
+
+            if let Some(mark) = family
+                .marks()
+                .iter()
+                .find(|mark| mark.holds(value, other_value))
+            {
+                marks.push((cell, other, *mark));
+            }
+        }
+
+        Ok(EdgeConstraint::new(family, layout, &marks, true)?)
+    }
+
+    /// Marks given by a section named after their family, one per line: the symbol of the mark
+    /// then the `line:column` of both boxes, such as `w 0:0 0:1`, and a `negative` line when
+    /// every possible mark is given
+    pub fn from_section(section: &Section, layout: &Layout) -> Result<Self, InvalidConstraint> {
+        let Some(family) = EdgeFamily::from_name(&section.name) else {
+            return Err(InvalidConstraint::new(format!(
+                "[{}] is not an edge section",
+                section.name
+            )));
+        };
+
+        let mut marks = vec![];
+        let mut negative = false;
+
+        for line in section.lines.iter() {
+            if line.eq_ignore_ascii_case(NEGATIVE_LINE) {
+                negative = true;
+                continue;
+            }
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+
+            let mark = match words.as_slice() {
+                [symbol, cell, other] => EdgeMark::from_symbol(symbol).and_then(|mark| {
+                    Some((parse_cell(cell, layout)?, parse_cell(other, layout)?, mark))
+                }),
+                _ => None,
+            };
+
+            match mark {
+                Some(mark) => marks.push(mark),
+                None => {
+                    return Err(InvalidConstraint::new(format!(
+                        "wrong {} mark '{line}'",
+                        family.name()
+                    )))
+                }
+            }
+        }
+
+        EdgeConstraint::new(family, layout, &marks, negative)
+    }
+
+    //////////
+    // Accessors
+
+    pub fn family(&self) -> EdgeFamily {
+        self.family
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    //////////
+    // Methods
+
+    /// Mark between two boxes, None if they are not neighbours or have none
+    pub fn mark_between(&self, cell: usize, other: usize) -> Option<EdgeMark> {
+        match self.edge(cell, other)? {
+            (first, true) => self.right[first],
+            (first, false) => self.down[first],
+        }
+    }
+
+    /// Slot of the mark between two neighbours, None if they are not
+    fn edge_mut(&mut self, cell: usize, other: usize) -> Option<&mut Option<EdgeMark>> {
+        match self.edge(cell, other)? {
+            (first, true) => Some(&mut self.right[first]),
+            (first, false) => Some(&mut self.down[first]),
+        }
+    }
+
+    /// Upper or left box of two neighbours, and whether the other one is on its right rather than
+    /// below it
+    fn edge(&self, cell: usize, other: usize) -> Option<(usize, bool)> {
+        let (first, second) = (cell.min(other), cell.max(other));
+
+        if second >= self.right.len() {
+            None
+        } else if second == first + 1 && second % self.size != 0 {
+            Some((first, true))
+        } else if second == first + self.size {
+            Some((first, false))
+        } else {
+            None
+        }
+    }
+
+    /// Values of the box having at least one partner value fitting `fits`
+    fn values_fitting(&self, fits: impl Fn(u8, u8) -> bool, neighbour_value: u8) -> Candidates {
+        let size = self.size as u8;
+
+        (1..=size)
+            .filter(|value| match neighbour_value {
+                TO_BE_SOLVED => (1..=size).any(|other| other != *value && fits(*value, other)),
+                other => fits(*value, other),
+            })
+            .fold(0, |mask, value| mask | value_to_mask(value))
+    }
+}
+
+impl Constraint for EdgeConstraint {
+    fn name(&self) -> &str {
+        self.family.name()
+    }
+
+    fn prune(&self, board: &Board, index: usize, candidates: Candidates) -> Candidates {
+        let mut candidates = candidates;
+
+        for neighbour in neighbours(index, self.size) {
+            let neighbour_value = board.get(neighbour);
+
+            match self.mark_between(index, neighbour) {
+                Some(mark) => {
+                    candidates &= self
+                        .values_fitting(|value, other| mark.holds(value, other), neighbour_value)
+                }
+                None if self.negative && neighbour_value != TO_BE_SOLVED => {
+                    candidates &= !self.values_fitting(
+                        |value, other| {
+                            self.family
+                                .marks()
+                                .iter()
+                                .any(|mark| mark.holds(value, other))
+                        },
+                        neighbour_value,
+                    )
+                }
+                None => {}
+            }
+        }
+
+        candidates
+    }
+
+    fn to_section(&self) -> Section {
+        let mut lines: Vec<String> = neighbour_pairs(self.size)
+            .into_iter()
+            .filter_map(|(cell, other)| {
+                let mark = self.mark_between(cell, other)?;
+
+                Some(format!(
+                    "{} {} {}",
+                    mark.symbol(),
+                    cell_to_string(cell, self.size),
+                    cell_to_string(other, self.size)
+                ))
+            })
+            .collect();
+
+        if self.negative {
+            lines.push(NEGATIVE_LINE.to_string());
+        }
+
+        Section {
+            name: self.name().to_string(),
+            lines,
+        }
+    }
+}
+
+////////////////////
+
+/// Boxes sharing a side with the box
+fn neighbours(index: usize, size: usize) -> Vec<usize> {
+    let (line, column) = (index / size, index % size);
+    let mut neighbours = vec![];
+
+    if line > 0 {
+        neighbours.push(index - size);
+    }
+    if column > 0 {
+        neighbours.push(index - 1);
+    }
+    if column + 1 < size {
+        neighbours.push(index + 1);
+    }
+    if line + 1 < size {
+        neighbours.push(index + size);
+    }
+
+    neighbours
+}
+
+/// Every pair of boxes sharing a side, line by line, each box before its right then lower
+/// neighbour
+fn neighbour_pairs(size: usize) -> Vec<(usize, usize)> {
+    let mut pairs = vec![];
+
+    for index in 0..size * size {
+        if index % size + 1 < size {
+            pairs.push((index, index + 1));
+        }
+        if index + size < size * size {
+            pairs.push((index, index + size));
+        }
+    }
+
+    pairs
+}
@@ -0,0 +1,119 @@
+use super::{
+    constraint::InvalidConstraint,
+    generator::{DifficultyNotReached, GeneratingSudokuError, UniqueSolutionNotReachable},
+    layout::InvalidLayout,
+    solver::{BoxSolutionNotFound, NoSudokuSolutionFound},
+    validation::ValidationError,
+};
+
+use std::{error::Error, fmt, io};
+
+////////////////////////////////////////
+
+/// Every failure the library reports instead of panicking on user input
+#[derive(Debug)]
+pub enum SabitaError {
+    /// Content of a puzzle file or argument that can not be read
+    Parse(String),
+    /// Wrong number of lines or columns
+    Dimension(String),
+    /// Value above the size of the grid
+    ValueOutOfRange {
+        value: u8,
+        line: usize,
+        column: usize,
+    },
+    /// Values breaking a rule of the grid
+    Conflict(ValidationError),
+    Unsolvable,
+    /// File that can not be read or written
+    Io {
+        path: String,
+        error: io::Error,
+    },
+    /// Puzzle that could not be generated with the requested settings
+    Generation(String),
+    /// Feature not available for this kind of puzzle
+    Unsupported(String),
+}
+
+impl fmt::Display for SabitaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SabitaError::Parse(reason) => write!(f, "Parsing error: {reason}"),
+            SabitaError::Dimension(reason) => write!(f, "{reason}"),
+            SabitaError::ValueOutOfRange {
+                value,
+                line,
+                column,
+            } => write!(
+                f,
+                "Value '{value}' out of bound at position {line};{column}"
+            ),
+            SabitaError::Conflict(err) => write!(f, "{err}"),
+            SabitaError::Unsolvable => write!(f, "{NoSudokuSolutionFound}"),
+            SabitaError::Io { path, error } => write!(f, "Unable to use file '{path}': {error}"),
+            SabitaError::Generation(reason) => write!(f, "{reason}"),
+            SabitaError::Unsupported(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl Error for SabitaError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SabitaError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+//////////
+
+impl From<ValidationError> for SabitaError {
+    fn from(err: ValidationError) -> Self {
+        SabitaError::Conflict(err)
+    }
+}
+
+impl From<NoSudokuSolutionFound> for SabitaError {
+    fn from(_: NoSudokuSolutionFound) -> Self {
+        SabitaError::Unsolvable
+    }
+}
+
+impl From<BoxSolutionNotFound> for SabitaError {
+    fn from(_: BoxSolutionNotFound) -> Self {
+        SabitaError::Unsolvable
+    }
+}
+
+impl From<InvalidLayout> for SabitaError {
+    fn from(err: InvalidLayout) -> Self {
+        SabitaError::Parse(err.to_string())
+    }
+}
+
+impl From<InvalidConstraint> for SabitaError {
+    fn from(err: InvalidConstraint) -> Self {
+        SabitaError::Parse(err.to_string())
+    }
+}
+
+impl From<GeneratingSudokuError> for SabitaError {
+    fn from(err: GeneratingSudokuError) -> Self {
+        SabitaError::Generation(err.to_string())
+    }
+}
+
+impl From<UniqueSolutionNotReachable> for SabitaError {
+    fn from(err: UniqueSolutionNotReachable) -> Self {
+        SabitaError::Generation(err.to_string())
+    }
+}
+
+impl From<DifficultyNotReached> for SabitaError {
+    fn from(err: DifficultyNotReached) -> Self {
+        SabitaError::Generation(err.to_string())
+    }
+}
@@ -1,56 +1,339 @@
-use super::grid::GridValues;
+use super::{
+    constants::{MAX_LENGTH_DIMENSION, TO_BE_SOLVED},
+    error::SabitaError,
+    grid::GridValues,
+};
 
-use std::fs;
+use std::{fmt, fs};
 
 ////////////////////////////////////////
 
-pub fn read(path: String) -> GridValues {
+/// Blank box of the line format, `0` being read as one too
+const LINE_BLANK: char = '.';
+
+/// Start of a comment line in a collection
+const COMMENT_PREFIX: char = '#';
+
+////////////////////////////////////////
+
+/// Way a puzzle is written
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Format {
+    /// A line of values separated by commas per line of the grid, followed by sections
+    #[default]
+    Grid,
+    /// Every value of the puzzle on a single line, such as the 81 characters of a 9x9 grid, one
+    /// puzzle per line in a collection
+    Line,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "grid" => Some(Format::Grid),
+            "line" => Some(Format::Line),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Format::Grid => "grid",
+            Format::Line => "line",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+//////////
+
+/// Block of a puzzle file following the values, introduced by a `[name]` line, such as the
+/// `[regions]` of a jigsaw sudoku
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub name: String,
+    /// Lines of the block, trimmed, empty ones left out
+    pub lines: Vec<String>,
+}
+
+////////////////////////////////////////
+
+pub fn read(path: String) -> Result<GridValues, SabitaError> {
+    Ok(read_with_sections(path)?.0)
+}
+
+/// Reads the values of the grid, then every section following them
+/// A file in the line format has no section, and must hold a single puzzle
+pub fn read_with_sections(path: String) -> Result<(GridValues, Vec<Section>), SabitaError> {
     let mut values: GridValues = vec![];
+    let mut sections: Vec<Section> = vec![];
 
-    let contents = fs::read_to_string(path).expect("Unable to read file '{path}'");
+    let contents = read_to_string(&path)?;
+
+    if is_line_format(&contents) {
+        let mut collection = parse_collection(&contents)?;
+
+        return match collection.len() {
+            1 => Ok((collection.remove(0), sections)),
+            nb_puzzles => Err(SabitaError::Parse(format!(
+                "'{path}' is a collection of {nb_puzzles} puzzles"
+            ))),
+        };
+    }
 
     let lines = contents.split("\n");
 
     for (line_index, line) in lines.enumerate() {
-        let space_trimmed = line.replace(" ", "");
-        let splitted = space_trimmed.split(",");
+        let trimmed = line.trim();
 
-        let mut val_line: Vec<u8> = vec![];
+        if let Some(name) = trimmed
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            sections.push(Section {
+                name: name.trim().to_string(),
+                lines: vec![],
+            });
+            continue;
+        }
 
-        for (col_index, val) in splitted.enumerate() {
-            if val.is_empty() {
-                break;
-            }
+        match sections.last_mut() {
+            Some(section) if !trimmed.is_empty() => section.lines.push(trimmed.to_string()),
+            Some(_) => {}
+            None => {
+                let val_line = parse_values_line(line, line_index)?;
 
-            match (val).parse::<u8>() {
-                Ok(number) => val_line.push(number),
-                Err(err) => panic!(
-                    "Parsing file error, wrong value '{val}' at position [{line_index}:{col_index}]: {}",
-                    err
-                ),
-            };
+                if !val_line.is_empty() {
+                    values.push(val_line);
+                }
+            }
         }
+    }
 
-        if !val_line.is_empty() {
-            values.push(val_line);
-        }
+    Ok((values, sections))
+}
+
+/// Reads every puzzle of a file in the line format, one per line
+/// A file in the grid format is a collection of its single grid
+pub fn read_collection(path: String) -> Result<Vec<GridValues>, SabitaError> {
+    let contents = read_to_string(&path)?;
+
+    if is_line_format(&contents) {
+        parse_collection(&contents)
+    } else {
+        Ok(vec![read(path)?])
     }
+}
+
+/// Puzzle of each line of a file in the line format, left unparsed along with its line number
+/// starting from 1, so that a single wrong line does not prevent reading the others
+pub fn read_collection_lines(path: String) -> Result<Vec<(usize, String)>, SabitaError> {
+    let contents = read_to_string(&path)?;
 
+    Ok(puzzle_lines(&contents)
+        .map(|(number, line)| (number, first_word(line).to_string()))
+        .collect())
+}
+
+/// Checks a file holds puzzles in the line format
+pub fn is_line_file(path: String) -> Result<bool, SabitaError> {
+    Ok(is_line_format(&read_to_string(&path)?))
+}
+
+/// Reads a puzzle written on a single line, its size being the square root of the number of
+/// characters, `.` or `0` for blanks
+pub fn parse_line(line: &str) -> Result<GridValues, SabitaError> {
+    let chars: Vec<char> = line.trim().chars().collect();
+    let Some(size) = (1..=MAX_LENGTH_DIMENSION as usize).find(|size| size * size == chars.len())
+    else {
+        return Err(SabitaError::Dimension(format!(
+            "Line of {} characters is not a square grid",
+            chars.len()
+        )));
+    };
+
+    let values = chars
+        .iter()
+        .map(|char| match char {
+            &LINE_BLANK => Ok(TO_BE_SOLVED),
+            _ => parse_value(&char.to_string()).ok_or_else(|| {
+                SabitaError::Parse(format!("wrong value '{char}' in line '{line}'"))
+            }),
+        })
+        .collect::<Result<Vec<u8>, SabitaError>>()?;
+
+    Ok(values.chunks(size).map(|row| row.to_vec()).collect())
+}
+
+/// Writes a puzzle on a single line, `.` for blanks
+pub fn values_to_line(values: &GridValues) -> String {
     values
+        .iter()
+        .flatten()
+        .map(|value| match *value {
+            TO_BE_SOLVED => LINE_BLANK.to_string(),
+            value => value_to_string(value),
+        })
+        .collect()
+}
+
+/// Writes the puzzles in the line format, one per line
+pub fn write_collection(path: String, collection: &[GridValues]) -> Result<(), SabitaError> {
+    let content: String = collection
+        .iter()
+        .map(|values| values_to_line(values) + "\n")
+        .collect();
+
+    fs::write(&path, content).map_err(|error| SabitaError::Io { path, error })
 }
 
-pub fn write(path: String, values: GridValues) {
+/// Reads the lines of a section written like the values of a grid
+pub fn parse_rows(section: &Section) -> Result<GridValues, SabitaError> {
+    section
+        .lines
+        .iter()
+        .enumerate()
+        .map(|(line_index, line)| parse_values_line(line, line_index))
+        .collect()
+}
+
+pub fn write(path: String, values: GridValues) -> Result<(), SabitaError> {
+    write_with_sections(path, values, &[])
+}
+
+pub fn write_with_sections(
+    path: String,
+    values: GridValues,
+    sections: &[Section],
+) -> Result<(), SabitaError> {
     let mut content: String = String::new();
 
-    for line in values {
-        let val_line: String = line
-            .into_iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+    for line in rows_to_lines(&values) {
+        content += &(line + "\n");
+    }
+
+    for section in sections {
+        content += &format!("\n[{}]\n", section.name);
 
-        content += &(val_line + "\n");
+        for line in section.lines.iter() {
+            content += &format!("{line}\n");
+        }
     }
 
-    fs::write(path, content).expect("Unable to write into file '{path}'");
+    fs::write(&path, content).map_err(|error| SabitaError::Io { path, error })
+}
+
+/// Lines of values separated by commas, as written in a file
+pub fn rows_to_lines(values: &GridValues) -> Vec<String> {
+    values
+        .iter()
+        .map(|line| {
+            line.iter()
+                .copied()
+                .map(value_to_string)
+                .collect::<Vec<String>>()
+                .join(",")
+        })
+        .collect()
+}
+
+fn parse_values_line(line: &str, line_index: usize) -> Result<Vec<u8>, SabitaError> {
+    let space_trimmed = line.replace(" ", "");
+    let splitted = space_trimmed.split(",");
+
+    let mut val_line: Vec<u8> = vec![];
+
+    for (col_index, val) in splitted.enumerate() {
+        if val.is_empty() {
+            break;
+        }
+
+        match parse_value(val) {
+            Some(number) => val_line.push(number),
+            None => {
+                return Err(SabitaError::Parse(format!(
+                    "wrong value '{val}' at position [{line_index}:{col_index}]"
+                )))
+            }
+        };
+    }
+
+    Ok(val_line)
+}
+
+////////////////////
+
+fn read_to_string(path: &str) -> Result<String, SabitaError> {
+    fs::read_to_string(path).map_err(|error| SabitaError::Io {
+        path: path.to_string(),
+        error,
+    })
+}
+
+/// Lines of a collection holding a puzzle along with their number starting from 1, without
+/// comments or blank lines
+fn puzzle_lines(contents: &str) -> impl Iterator<Item = (usize, &str)> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with(COMMENT_PREFIX))
+}
+
+/// Lines of values of the grid format always hold commas, unlike the line format
+fn is_line_format(contents: &str) -> bool {
+    match puzzle_lines(contents).next() {
+        Some((_, line)) => !line.contains(',') && !line.starts_with('['),
+        None => false,
+    }
+}
+
+/// Puzzles of a collection, anything following the puzzle on its line being left out, such as
+/// a rating
+fn parse_collection(contents: &str) -> Result<Vec<GridValues>, SabitaError> {
+    puzzle_lines(contents)
+        .map(|(number, line)| parse_line(first_word(line)).map_err(|err| at_line(err, number)))
+        .collect()
+}
+
+fn first_word(line: &str) -> &str {
+    line.split_whitespace().next().unwrap_or_default()
+}
+
+/// Error of a line of a collection, telling its number
+fn at_line(err: SabitaError, number: usize) -> SabitaError {
+    match err {
+        SabitaError::Parse(reason) => SabitaError::Parse(format!("line {number}: {reason}")),
+        SabitaError::Dimension(reason) => {
+            SabitaError::Dimension(format!("line {number}: {reason}"))
+        }
+        err => err,
+    }
+}
+
+/// Reads a value written as a number, or as a letter from `A` for values above 9
+fn parse_value(text: &str) -> Option<u8> {
+    if let Ok(number) = text.parse::<u8>() {
+        return Some(number);
+    }
+
+    let mut chars = text.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) if letter.is_ascii_alphabetic() => {
+            Some(letter.to_ascii_uppercase() as u8 - b'A' + 10)
+        }
+        _ => None,
+    }
+}
+
+/// Writes values above 9 as letters, so that a 16x16 grid uses 1 to 9 then A to G
+pub fn value_to_string(value: u8) -> String {
+    match value {
+        0..=9 => value.to_string(),
+        _ => ((b'A' + value - 10) as char).to_string(),
+    }
 }
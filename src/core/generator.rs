@@ -1,14 +1,39 @@
-use crate::{assets::full_grid::GRID_VALUES_1, utils::grid_utils::grid_values_array_to_vec};
-
 use super::{
-    constants::{LENGTH_DIMENSION, MAX_NB_VALUES, TO_BE_SOLVED},
-    grid::{location_to_region, BoxLocation, GridValues},
-    solver::{locate_missing_box, solve},
+    board::{mask_to_values, Board, Candidates},
+    canonical::fingerprint,
+    constants::{LENGTH_DIMENSION, MAX_NB_VALUES, MINIMUM_PROVIDED, TO_BE_SOLVED},
+    constraint::Constraint,
+    date::Date,
+    error::SabitaError,
+    file::values_to_line,
+    grid::{check_dimensions, wrong_number_of_lines, BoxLocation, GridValues},
+    killer::{Cage, Killer},
+    layout::Layout,
+    multigrid::{MultiBoard, MultiLayout},
+    rating::{rate, Level},
+    solver::{board_has_unique_solution, has_unique_solution, Solutions},
 };
 
-use rand::{distr::Uniform, rng, Rng};
-
-use std::fmt;
+use rand::{
+    distr::Uniform,
+    rng,
+    seq::{IndexedRandom, SliceRandom},
+    Rng, SeedableRng,
+};
+use rand_chacha::ChaCha8Rng;
+
+use std::{
+    collections::HashSet,
+    fmt,
+    fs::File,
+    io::{BufWriter, Write},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{sync_channel, Receiver},
+        Arc,
+    },
+    thread,
+};
 
 ////////////////////////////////////////
 
@@ -21,126 +46,687 @@ impl fmt::Display for GeneratingSudokuError {
     }
 }
 
+#[derive(Debug)]
+pub struct UniqueSolutionNotReachable {
+    nb_to_remove: u16,
+}
+
+impl fmt::Display for UniqueSolutionNotReachable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Could not remove {} values while keeping a single solution",
+            self.nb_to_remove
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct DifficultyNotReached {
+    level: Level,
+}
+
+impl fmt::Display for DifficultyNotReached {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Could not generate a sudoku of level {}", self.level)
+    }
+}
+
+/// Puzzles left out by `generate_many`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GenerationReport {
+    /// Equivalent to a puzzle already written
+    pub nb_duplicates: usize,
+    /// Attempts which did not reach the number of removals or the difficulty
+    pub nb_failed: usize,
+}
+
+//////////
+
+/// Number of fresh removal orders tried before giving up on reaching a unique solution
+const MAX_UNIQUE_REMOVAL_ATTEMPTS: u8 = 20;
+
+/// Number of full grids tried before giving up on reaching a difficulty
+const MAX_DIFFICULTY_ATTEMPTS: u16 = 200;
+
+/// Number of fresh random fillings tried before giving up on generating a full grid
+const MAX_FILL_ATTEMPTS: u16 = 1000;
+
+/// Values placed per box of the grid before a filling starts over, random searches on irregular
+/// layouts sometimes wandering for minutes in a dead end while a new start succeeds at once
+const FILL_PLACEMENTS_PER_BOX: usize = 20;
+
+/// Boxes of the smallest and largest cages drawn for a killer sudoku, a cage only ending up smaller
+/// when its neighbours are all taken
+const MIN_CAGE_SIZE: usize = 2;
+const MAX_CAGE_SIZE: usize = 4;
+
+/// Puzzles a thread of `generate_many` can get ahead of the writer
+const PENDING_PUZZLES_PER_THREAD: usize = 4;
+
+/// Attempts of `generate_many` failing in a row before the settings are deemed out of reach
+const MAX_CONSECUTIVE_FAILED_ATTEMPTS: u16 = 50;
+
 ////////////////////////////////////////
 
-/// Generates a sudoku
-pub fn generate() -> Result<GridValues, GeneratingSudokuError> {
-    let original: GridValues = grid_values_array_to_vec(GRID_VALUES_1);
-    let to_return = original.clone();
+/// Random generator giving back the same puzzles for the same seed, on any machine running the
+/// same version of the crate
+/// ChaCha8 is named rather than `StdRng`, whose algorithm may change with any release of rand
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
 
-    let mut modified = remove_random_values(&to_return, 50).0;
-    random_permutations(&mut modified);
-    swap_lines(&mut modified);
+/// Fresh seed, for runs not given one
+pub fn random_seed() -> u64 {
+    rng().random()
+}
 
-    let missing_locations = locate_missing_box(&modified);
-    let to_return = solve(&modified, &missing_locations).unwrap();
+////////////////////
+
+/// Generates a full sudoku, filling an empty board by backtracking with candidates tried in a
+/// random order, so that any valid grid can come out
+pub fn generate<R: Rng>(generator: &mut R) -> Result<GridValues, GeneratingSudokuError> {
+    generate_with_layout(&Layout::standard(), generator)
+}
 
-    Ok(to_return)
+/// Generates a full grid of any size and layout
+pub fn generate_with_layout<R: Rng>(
+    layout: &Layout,
+    generator: &mut R,
+) -> Result<GridValues, GeneratingSudokuError> {
+    generate_with_constraints(layout, &[], generator)
+}
+
+/// Generates a full grid of any layout following the variant constraints
+pub fn generate_with_constraints<R: Rng>(
+    layout: &Layout,
+    constraints: &[Arc<dyn Constraint>],
+    generator: &mut R,
+) -> Result<GridValues, GeneratingSudokuError> {
+    for _ in 0..MAX_FILL_ATTEMPTS {
+        let mut board = Board::empty(layout).with_constraints(constraints);
+        let mut budget = layout.nb_boxes() * FILL_PLACEMENTS_PER_BOX;
+
+        if fill_randomly(&mut board, generator, &mut budget) {
+            return Ok(board.get_values());
+        }
+    }
+
+    Err(GeneratingSudokuError)
+}
+
+/// Generates every grid of an overlapping puzzle at once, shared boxes holding the same value
+pub fn generate_multi<R: Rng>(
+    layout: &MultiLayout,
+    generator: &mut R,
+) -> Result<GridValues, GeneratingSudokuError> {
+    let empty = vec![vec![TO_BE_SOLVED; layout.width()]; layout.height()];
+    let nb_boxes = layout.height() * layout.width();
+
+    for _ in 0..MAX_FILL_ATTEMPTS {
+        let mut board = MultiBoard::new(&empty, layout);
+        let mut budget = nb_boxes * FILL_PLACEMENTS_PER_BOX;
+
+        if fill_randomly(&mut board, generator, &mut budget) {
+            return Ok(board.get_values());
+        }
+    }
+
+    Err(GeneratingSudokuError)
+}
+
+/// Boards the random filling works on
+trait Fillable {
+    fn most_constrained(&self) -> Option<(usize, Candidates)>;
+    fn place(&mut self, index: usize, value: u8);
+    fn remove(&mut self, index: usize);
+}
+
+impl Fillable for Board {
+    fn most_constrained(&self) -> Option<(usize, Candidates)> {
+        Board::most_constrained(self)
+    }
+
+    fn place(&mut self, index: usize, value: u8) {
+        Board::place(self, index, value)
+    }
+
+    fn remove(&mut self, index: usize) {
+        Board::remove(self, index)
+    }
+}
+
+impl Fillable for MultiBoard {
+    fn most_constrained(&self) -> Option<(usize, Candidates)> {
+        MultiBoard::most_constrained(self)
+    }
+
+    fn place(&mut self, index: usize, value: u8) {
+        MultiBoard::place(self, index, value)
+    }
+
+    fn remove(&mut self, index: usize) {
+        MultiBoard::remove(self, index)
+    }
+}
+
+/// Depth first search on the most constrained box, trying its candidates in a random order
+/// Gives up once `budget` values have been placed
+fn fill_randomly<B: Fillable, R: Rng>(
+    board: &mut B,
+    generator: &mut R,
+    budget: &mut usize,
+) -> bool {
+    let Some((index, candidates)) = board.most_constrained() else {
+        return true;
+    };
+
+    let mut values = mask_to_values(candidates);
+    values.shuffle(generator);
+
+    for value in values {
+        if *budget == 0 {
+            return false;
+        }
+        *budget -= 1;
+
+        board.place(index, value);
+
+        if fill_randomly(board, generator, budget) {
+            return true;
+        }
+
+        board.remove(index);
+    }
+
+    false
 }
 
 ////////////////////
 
-pub fn remove_random_values(
+pub fn remove_random_values<R: Rng>(
     values: &GridValues,
-    nb_to_remove: u8,
-) -> (GridValues, Vec<BoxLocation>) {
-    if nb_to_remove >= MAX_NB_VALUES {
-        panic!("Can not remove that much values")
+    nb_to_remove: u16,
+    generator: &mut R,
+) -> Result<(GridValues, Vec<BoxLocation>), SabitaError> {
+    remove_random_values_with_layout(values, &inferred_layout(values)?, nb_to_remove, generator)
+}
+
+pub fn remove_random_values_with_layout<R: Rng>(
+    values: &GridValues,
+    layout: &Layout,
+    nb_to_remove: u16,
+    generator: &mut R,
+) -> Result<(GridValues, Vec<BoxLocation>), SabitaError> {
+    check_dimensions(values, layout)?;
+
+    if nb_to_remove as usize >= layout.nb_boxes() {
+        return Err(SabitaError::Generation(format!(
+            "Can not remove {nb_to_remove} values out of {} boxes",
+            layout.nb_boxes()
+        )));
     }
 
     let mut matrix = values.clone();
 
-    let mut generator = rng();
-    let pos = Uniform::try_from(TO_BE_SOLVED..LENGTH_DIMENSION).unwrap();
+    let pos = Uniform::try_from(0..layout.size()).unwrap();
 
     let mut loc_removed = vec![];
 
     while loc_removed.len() < nb_to_remove.into() {
-        let line = generator.sample(pos) as usize;
-        let column = generator.sample(pos) as usize;
+        let line = generator.sample(pos);
+        let column = generator.sample(pos);
 
         let location = BoxLocation {
             line,
             column,
-            region: location_to_region(&line, &column).unwrap(),
+            region: layout.region_of(line, column),
         };
 
         if !loc_removed.contains(&location) {
-            matrix[line as usize][column as usize] = TO_BE_SOLVED;
+            matrix[line][column] = TO_BE_SOLVED;
 
             loc_removed.push(location);
         }
     }
 
-    (matrix, loc_removed)
+    Ok((matrix, loc_removed))
+}
+
+/// Removes values one box at a time in a random order, putting a value back whenever its removal
+/// would let the grid have more than one solution
+/// If every box has been tried without reaching `nb_to_remove`, starts over with a new order
+pub fn remove_values_keeping_unique<R: Rng>(
+    values: &GridValues,
+    nb_to_remove: u16,
+    generator: &mut R,
+) -> Result<(GridValues, Vec<BoxLocation>), SabitaError> {
+    remove_values_keeping_unique_with_layout(
+        values,
+        &inferred_layout(values)?,
+        nb_to_remove,
+        generator,
+    )
+}
+
+pub fn remove_values_keeping_unique_with_layout<R: Rng>(
+    values: &GridValues,
+    layout: &Layout,
+    nb_to_remove: u16,
+    generator: &mut R,
+) -> Result<(GridValues, Vec<BoxLocation>), SabitaError> {
+    remove_values_keeping_unique_with_constraints(values, layout, &[], nb_to_remove, generator)
+}
+
+/// Removes values keeping a single solution under the variant constraints, which often allow far
+/// fewer clues than a classic grid
+pub fn remove_values_keeping_unique_with_constraints<R: Rng>(
+    values: &GridValues,
+    layout: &Layout,
+    constraints: &[Arc<dyn Constraint>],
+    nb_to_remove: u16,
+    generator: &mut R,
+) -> Result<(GridValues, Vec<BoxLocation>), SabitaError> {
+    check_dimensions(values, layout)?;
+
+    if nb_to_remove as usize >= layout.nb_boxes() {
+        return Err(UniqueSolutionNotReachable { nb_to_remove }.into());
+    }
+
+    // The bound is only known for the classic grid
+    if layout.is_standard()
+        && constraints.is_empty()
+        && MAX_NB_VALUES as u16 - nb_to_remove < MINIMUM_PROVIDED as u16
+    {
+        return Err(UniqueSolutionNotReachable { nb_to_remove }.into());
+    }
+
+    let mut positions = all_positions(layout.size());
+
+    for _ in 0..MAX_UNIQUE_REMOVAL_ATTEMPTS {
+        positions.shuffle(generator);
+
+        let mut board = Board::with_layout(values, layout).with_constraints(constraints);
+        let mut loc_removed = vec![];
+
+        for (line, column) in positions.iter().copied() {
+            if loc_removed.len() >= nb_to_remove.into() {
+                break;
+            }
+
+            let index = line * layout.size() + column;
+            let removed_value = board.get(index);
+
+            if removed_value == TO_BE_SOLVED {
+                continue;
+            }
+
+            board.remove(index);
+
+            if board_has_unique_solution(&board) {
+                loc_removed.push(board.location(index));
+            } else {
+                board.place(index, removed_value);
+            }
+        }
+
+        if loc_removed.len() == nb_to_remove.into() {
+            return Ok((board.get_values(), loc_removed));
+        }
+    }
+
+    Err(UniqueSolutionNotReachable { nb_to_remove }.into())
 }
 
-/// As swaping first and second line wont change the solution for their regions and columns
-/// they can be swapped in order to create new sudokus, same for every line and columns within
-/// their regions
-fn swap_lines(values: &mut GridValues) {
-    let first_line = values[0].clone();
-    let second_line = values[1].clone();
+/// Removes values of an overlapping puzzle one box at a time in a random order, putting a value
+/// back whenever its removal would let the puzzle have more than one solution
+pub fn remove_values_keeping_unique_multi<R: Rng>(
+    values: &GridValues,
+    layout: &MultiLayout,
+    nb_to_remove: u16,
+    generator: &mut R,
+) -> Result<GridValues, UniqueSolutionNotReachable> {
+    let mut board = MultiBoard::new(values, layout);
+
+    let mut indices: Vec<usize> = (0..layout.height() * layout.width())
+        .filter(|index| board.get(*index) != TO_BE_SOLVED)
+        .collect();
+
+    if nb_to_remove as usize >= indices.len() {
+        return Err(UniqueSolutionNotReachable { nb_to_remove });
+    }
+
+    for _ in 0..MAX_UNIQUE_REMOVAL_ATTEMPTS {
+        indices.shuffle(generator);
 
-    let fourth_line = values[3].clone();
-    let fifth_line = values[4].clone();
+        board = MultiBoard::new(values, layout);
+        let mut nb_removed = 0;
 
-    let seventh_line = values[6].clone();
-    let eighth_line = values[7].clone();
+        for index in indices.iter().copied() {
+            if nb_removed >= nb_to_remove {
+                break;
+            }
 
-    values[0] = second_line;
-    values[1] = first_line;
+            let removed_value = board.get(index);
+            board.remove(index);
 
-    values[3] = fifth_line;
-    values[4] = fourth_line;
+            if board.count_solutions(2) == 1 {
+                nb_removed += 1;
+            } else {
+                board.place(index, removed_value);
+            }
+        }
 
-    values[6] = eighth_line;
-    values[7] = seventh_line;
+        if nb_removed == nb_to_remove {
+            return Ok(board.get_values());
+        }
+    }
+
+    Err(UniqueSolutionNotReachable { nb_to_remove })
 }
 
-fn random_permutations(values: &mut GridValues) {
-    let mut generator = rng();
-    let value = Uniform::try_from(1..LENGTH_DIMENSION + 1).unwrap();
+/// Removes values from fresh full grids until the rating of the puzzle falls in the `level` band,
+/// always keeping a single solution
+/// A value whose removal overshoots the band is put back, and the generator starts over from a new
+/// full grid when every box has been tried without reaching the band
+pub fn generate_with_difficulty<R: Rng>(
+    level: Level,
+    generator: &mut R,
+) -> Result<GridValues, DifficultyNotReached> {
+    let mut positions = all_positions(LENGTH_DIMENSION as usize);
+
+    for _ in 0..MAX_DIFFICULTY_ATTEMPTS {
+        let Ok(mut matrix) = generate(generator) else {
+            continue;
+        };
+
+        positions.shuffle(generator);
+
+        let mut current_level = Level::Easy;
 
-    let mut available_values = vec![];
-    for i in 0..(LENGTH_DIMENSION) {
-        for j in 0..(LENGTH_DIMENSION) {
-            let line = i as usize;
-            let col = j as usize;
+        for (line, column) in positions.iter().copied() {
+            let removed_value = matrix[line][column];
+            matrix[line][column] = TO_BE_SOLVED;
 
-            let val = values[line][col];
+            let new_level = match has_unique_solution(&matrix) {
+                Ok(true) => rate(&matrix).ok().map(|rating| rating.level),
+                _ => None,
+            };
 
-            if !available_values.contains(&val) {
-                available_values.push(val);
+            match new_level {
+                Some(new_level) if new_level <= level => current_level = new_level,
+                _ => matrix[line][column] = removed_value,
             }
         }
+
+        if current_level == level {
+            return Ok(matrix);
+        }
     }
 
-    let available_pairs = (available_values.len() / 2) as u8; // Already floored by u8 type
-    let nb_permutations = generator.random_range(2..available_pairs);
+    Err(DifficultyNotReached { level })
+}
+
+/// Generates a killer sudoku: a full grid is cut into random cages of connected boxes, then the
+/// values where two solutions still differ are given until a single one is left, which usually
+/// takes few or no given values
+pub fn generate_killer<R: Rng>(
+    layout: &Layout,
+    generator: &mut R,
+) -> Result<(GridValues, Killer), GeneratingSudokuError> {
+    let full = generate_with_layout(layout, generator)?;
+    let killer = Killer::new(layout, random_cages(&full, layout, generator))
+        .map_err(|_| GeneratingSudokuError)?;
+
+    let constraints: Vec<Arc<dyn Constraint>> = vec![Arc::new(killer.clone())];
+    let mut puzzle = vec![vec![TO_BE_SOLVED; layout.size()]; layout.size()];
+
+    loop {
+        let board = Board::with_layout(&puzzle, layout).with_constraints(&constraints);
+        let solutions: Vec<GridValues> = Solutions::from_board(board).take(2).collect();
+
+        let [first, second] = solutions.as_slice() else {
+            return Ok((puzzle, killer));
+        };
 
-    for _ in 0..nb_permutations {
-        let value_a = generator.sample(value);
-        let value_b = generator.sample(value);
+        let differing: Vec<(usize, usize)> = all_positions(layout.size())
+            .into_iter()
+            .filter(|(line, column)| first[*line][*column] != second[*line][*column])
+            .collect();
 
-        permute_values(values, value_a, value_b);
+        let Some((line, column)) = differing.choose(generator).copied() else {
+            return Err(GeneratingSudokuError);
+        };
+
+        puzzle[line][column] = full[line][column];
     }
 }
 
-/// Replace every instance of value_a with value_b and vice_versa
-pub fn permute_values(values: &mut GridValues, value_a: u8, value_b: u8) {
-    for i in 0..(LENGTH_DIMENSION) {
-        for j in 0..(LENGTH_DIMENSION) {
-            let line = i as usize;
-            let col = j as usize;
+/// Cuts a full grid into cages of connected boxes holding distinct values
+fn random_cages<R: Rng>(full: &GridValues, layout: &Layout, generator: &mut R) -> Vec<Cage> {
+    let size = layout.size();
+    let mut positions = all_positions(size);
+    positions.shuffle(generator);
+
+    let mut in_cage = vec![vec![false; size]; size];
+    let mut cages = vec![];
 
-            let val = values[line][col];
+    for (line, column) in positions {
+        if in_cage[line][column] {
+            continue;
+        }
+
+        let target_size = generator.random_range(MIN_CAGE_SIZE..=MAX_CAGE_SIZE);
+        let mut cells = vec![(line, column)];
+        in_cage[line][column] = true;
+
+        while cells.len() < target_size {
+            let neighbours: Vec<(usize, usize)> = cells
+                .iter()
+                .flat_map(|(line, column)| {
+                    [
+                        (line.wrapping_sub(1), *column),
+                        (line + 1, *column),
+                        (*line, column.wrapping_sub(1)),
+                        (*line, column + 1),
+                    ]
+                })
+                .filter(|(line, column)| *line < size && *column < size)
+                .filter(|(line, column)| !in_cage[*line][*column])
+                .filter(|(line, column)| {
+                    cells.iter().all(|(cell_line, cell_column)| {
+                        full[*cell_line][*cell_column] != full[*line][*column]
+                    })
+                })
+                .collect();
+
+            let Some((line, column)) = neighbours.choose(generator).copied() else {
+                break;
+            };
+
+            cells.push((line, column));
+            in_cage[line][column] = true;
+        }
+
+        let sum = cells
+            .iter()
+            .map(|(line, column)| u32::from(full[*line][*column]))
+            .sum();
+        let mut cells: Vec<usize> = cells
+            .iter()
+            .map(|(line, column)| line * size + column)
+            .collect();
+        cells.sort_unstable();
+
+        cages.push(Cage::new(cells, sum));
+    }
 
-            if val == value_a {
-                values[line][col] = value_b;
-            } else if val == value_b {
-                values[line][col] = value_a;
+    cages.sort_by_key(|cage| cage.cells()[0]);
+
+    cages
+}
+
+/// Puzzle of the day, the same on every install as its seed only depends on the date
+pub fn daily(date: &Date, difficulty: Level) -> Result<GridValues, DifficultyNotReached> {
+    generate_with_difficulty(difficulty, &mut seeded_rng(daily_seed(date)))
+}
+
+/// Seed of the puzzle of the day, the date read as the number YYYYMMDD
+pub fn daily_seed(date: &Date) -> u64 {
+    u64::from(date.year()) * 10_000 + u64::from(date.month()) * 100 + u64::from(date.day())
+}
+
+/// Generates `count` distinct classic puzzles on `nb_threads` threads, writing each one on its own
+/// line of the collection file `path` as soon as it is ready
+/// Puzzles equivalent through the sudoku symmetries count as duplicates, and failed attempts are
+/// skipped until too many of them happen in a row
+/// The n-th attempt is generated from `seed + n`, so one thread always gives back the same puzzles
+/// while several threads may write them in another order
+pub fn generate_many(
+    count: usize,
+    nb_threads: usize,
+    nb_to_remove: Option<u16>,
+    difficulty: Option<Level>,
+    seed: u64,
+    path: String,
+) -> Result<GenerationReport, SabitaError> {
+    let file = File::create(&path).map_err(|error| SabitaError::Io {
+        path: path.clone(),
+        error,
+    })?;
+    let mut output = BufWriter::new(file);
+
+    let next_attempt = AtomicU64::new(0);
+    let done = AtomicBool::new(false);
+
+    let (sender, receiver) = sync_channel(nb_threads.max(1) * PENDING_PUZZLES_PER_THREAD);
+
+    thread::scope(|scope| {
+        for _ in 0..nb_threads.max(1) {
+            let sender = sender.clone();
+            let (next_attempt, done) = (&next_attempt, &done);
+
+            scope.spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    let attempt = next_attempt.fetch_add(1, Ordering::Relaxed);
+                    let mut generator = seeded_rng(seed.wrapping_add(attempt));
+
+                    let puzzle = generate_puzzle(nb_to_remove, difficulty, &mut generator)
+                        .map(|values| (fingerprint(&values), values));
+
+                    // The writer is gone once it has every puzzle or met an error
+                    if sender.send(puzzle).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(sender);
+
+        let result = write_distinct(count, receiver, &mut output, &path);
+        done.store(true, Ordering::Relaxed);
+
+        result
+    })
+    .and_then(|report| {
+        output
+            .flush()
+            .map_err(|error| SabitaError::Io { path, error })?;
+
+        Ok(report)
+    })
+}
+
+/// Keeps the first puzzle of each fingerprint until `count` of them have been written
+/// Fails with the last error once `MAX_CONSECUTIVE_FAILED_ATTEMPTS` attempts failed in a row
+fn write_distinct<W: Write>(
+    count: usize,
+    receiver: Receiver<Result<(String, GridValues), SabitaError>>,
+    output: &mut W,
+    path: &str,
+) -> Result<GenerationReport, SabitaError> {
+    let mut fingerprints = HashSet::new();
+    let mut report = GenerationReport::default();
+    let mut nb_failed_in_a_row = 0;
+
+    while fingerprints.len() < count {
+        let Ok(puzzle) = receiver.recv() else {
+            break;
+        };
+
+        let (fingerprint, values) = match puzzle {
+            Ok(puzzle) => puzzle,
+            Err(err) => {
+                report.nb_failed += 1;
+                nb_failed_in_a_row += 1;
+
+                if nb_failed_in_a_row >= MAX_CONSECUTIVE_FAILED_ATTEMPTS {
+                    return Err(err);
+                }
+
+                continue;
             }
+        };
+        nb_failed_in_a_row = 0;
+
+        if !fingerprints.insert(fingerprint) {
+            report.nb_duplicates += 1;
+            continue;
+        }
+
+        writeln!(output, "{}", values_to_line(&values)).map_err(|error| SabitaError::Io {
+            path: path.to_string(),
+            error,
+        })?;
+    }
+
+    Ok(report)
+}
+
+/// Single classic puzzle, of the given difficulty or with the given number of values removed
+fn generate_puzzle<R: Rng>(
+    nb_to_remove: Option<u16>,
+    difficulty: Option<Level>,
+    generator: &mut R,
+) -> Result<GridValues, SabitaError> {
+    if let Some(level) = difficulty {
+        return Ok(generate_with_difficulty(level, generator)?);
+    }
+
+    let values = generate(generator)?;
+
+    match nb_to_remove {
+        Some(to_remove) => Ok(remove_values_keeping_unique(&values, to_remove, generator)?.0),
+        None => Ok(values),
+    }
+}
+
+/// Every (line, column) of a grid of the given size
+fn all_positions(size: usize) -> Vec<(usize, usize)> {
+    let mut positions = vec![];
+
+    for line in 0..size {
+        for column in 0..size {
+            positions.push((line, column));
+        }
+    }
+
+    positions
+}
+
+/// Usual layout for the size of the grid
+fn inferred_layout(values: &GridValues) -> Result<Layout, SabitaError> {
+    Layout::for_size(values.len()).ok_or_else(|| wrong_number_of_lines(values))
+}
+
+/// Replace every instance of value_a with value_b and vice_versa
+pub fn permute_values(values: &mut GridValues, value_a: u8, value_b: u8) {
+    for val in values.iter_mut().flatten() {
+        if *val == value_a {
+            *val = value_b;
+        } else if *val == value_b {
+            *val = value_a;
         }
     }
 }
@@ -1,14 +1,29 @@
 use crate::{assets::full_grid::ConstGridValues, utils::grid_utils::grid_values_array_to_vec};
 
 use super::{
-    constants::{LENGTH_DIMENSION, MAX_NB_VALUES, MINIMUM_PROVIDED},
-    file::{read, write},
-    generator::{generate, remove_random_values},
-    solver::{locate_missing_box, solve},
-    validation::validate,
+    board::Board,
+    canonical::{canonical_form, fingerprint},
+    constraint::{self, Constraint},
+    error::SabitaError,
+    file::{
+        is_line_file, read_collection, read_with_sections, values_to_line, write_collection,
+        write_with_sections, Format,
+    },
+    generator::{
+        generate_killer, generate_with_constraints, generate_with_difficulty,
+        remove_random_values_with_layout, remove_values_keeping_unique_with_constraints,
+    },
+    layout::{Layout, REGIONS_SECTION},
+    logic::{solve_logically, LogicalSolution},
+    rating::{rate, Level, Rating},
+    solver::{count_board_solutions, locate_missing_board_box, solve_board, Solutions, Solver},
+    transform::Transformation,
+    validation::{validate_all_with_constraints, validate_with_constraints, ValidationError},
 };
 
-use std::{error::Error, fmt};
+use rand::Rng;
+
+use std::{fmt, sync::Arc};
 
 ////////////////////////////////////////
 
@@ -43,6 +58,9 @@ impl PartialEq for BoxLocation {
 #[derive(Debug, Clone)]
 pub struct Grid {
     pub values: GridValues,
+    pub layout: Layout,
+    /// Rules of the variant on top of the lines, columns and regions
+    pub constraints: Vec<Arc<dyn Constraint>>,
 }
 
 ////////////////////
@@ -51,70 +69,121 @@ impl Grid {
     //////////
     // Constructor
 
-    pub fn new(values: GridValues) -> Self {
-        if values.len() != LENGTH_DIMENSION.into() {
-            panic!("Wrong number of lines: {}", values.len());
+    /// Grid of the usual layout for its size
+    pub fn new(values: GridValues) -> Result<Self, SabitaError> {
+        match Layout::for_size(values.len()) {
+            Some(layout) => Grid::with_layout(values, layout),
+            None => Err(wrong_number_of_lines(&values)),
         }
+    }
 
-        for (row_index, row) in values.iter().enumerate() {
-            if row.len() != LENGTH_DIMENSION.into() {
-                panic!(
-                    "Line index {} has a different number of columns than {}",
-                    row_index, LENGTH_DIMENSION
-                )
-            }
+    pub fn with_layout(values: GridValues, layout: Layout) -> Result<Self, SabitaError> {
+        Grid::with_constraints(values, layout, vec![])
+    }
 
-            for (column_index, value) in row.iter().enumerate() {
-                if row.len() > LENGTH_DIMENSION.into() {
-                    panic!(
-                        "Column index {} larger than {}",
-                        column_index, LENGTH_DIMENSION
-                    )
-                }
-
-                if value > &LENGTH_DIMENSION {
-                    panic!(
-                        "Value '{}' out of bound at position {};{}",
-                        value, row_index, column_index
-                    );
-                }
-            }
-        }
+    /// Grid of a sudoku variant, its values following the constraints
+    pub fn with_constraints(
+        values: GridValues,
+        layout: Layout,
+        constraints: Vec<Arc<dyn Constraint>>,
+    ) -> Result<Self, SabitaError> {
+        check_dimensions(&values, &layout)?;
+
+        validate_with_constraints(&values, &layout, &constraints)?;
+
+        Ok(Grid {
+            values,
+            layout,
+            constraints,
+        })
+    }
 
-        match validate(&values) {
-            Ok(_) => {}
-            Err(err) => panic!("{err}"),
+    /// Generates a full grid, then removes `nb_to_remove` values while keeping a single solution
+    /// With a difficulty, values are removed until the puzzle is rated at that level instead, so
+    /// both can not be given
+    /// Every random choice comes from `generator`, so a seeded one gives back the same grid
+    pub fn generate<R: Rng>(
+        nb_to_remove: Option<u16>,
+        difficulty: Option<Level>,
+        generator: &mut R,
+    ) -> Result<Self, SabitaError> {
+        match (nb_to_remove, difficulty) {
+            (Some(_), Some(_)) => Err(SabitaError::Generation(
+                "Either a number of values to remove or a difficulty can be given, not both"
+                    .to_string(),
+            )),
+            (_, Some(level)) => Grid::new(generate_with_difficulty(level, generator)?),
+            (_, None) => Grid::generate_with_layout(Layout::standard(), nb_to_remove, generator),
         }
+    }
 
-        Grid { values }
+    /// Generates a grid of any size and layout, keeping a single solution
+    pub fn generate_with_layout<R: Rng>(
+        layout: Layout,
+        nb_to_remove: Option<u16>,
+        generator: &mut R,
+    ) -> Result<Self, SabitaError> {
+        Grid::generate_with_constraints(layout, vec![], nb_to_remove, generator)
     }
 
-    pub fn generate(nb_to_remove: Option<u8>) -> Self {
-        let mut values = generate().unwrap();
+    /// Generates a grid of a sudoku variant, keeping a single solution under its constraints
+    pub fn generate_with_constraints<R: Rng>(
+        layout: Layout,
+        constraints: Vec<Arc<dyn Constraint>>,
+        nb_to_remove: Option<u16>,
+        generator: &mut R,
+    ) -> Result<Self, SabitaError> {
+        let mut values = generate_with_constraints(&layout, &constraints, generator)?;
 
         if let Some(to_remove) = nb_to_remove {
-            let remaining_cells = MAX_NB_VALUES - to_remove;
+            (values, _) = remove_values_keeping_unique_with_constraints(
+                &values,
+                &layout,
+                &constraints,
+                to_remove,
+                generator,
+            )?;
+        }
 
-            if remaining_cells < MINIMUM_PROVIDED {
-                println!("Carefull, {MINIMUM_PROVIDED} is considered the minimum number of values to provide to solve a sudoku");
-            }
+        Grid::with_constraints(values, layout, constraints)
+    }
 
-            values = remove_random_values(&values, to_remove).0;
-        }
+    /// Generates a killer sudoku, its cages leading to a single solution with few or no values given
+    pub fn generate_killer<R: Rng>(layout: Layout, generator: &mut R) -> Result<Self, SabitaError> {
+        let (values, killer) = generate_killer(&layout, generator)?;
 
-        Grid::new(values)
+        Grid::with_constraints(values, layout, vec![Arc::new(killer)])
     }
 
-    pub fn from_array(array: ConstGridValues) -> Self {
+    pub fn from_array(array: ConstGridValues) -> Result<Self, SabitaError> {
         let values = grid_values_array_to_vec(array);
 
         Grid::new(values)
     }
 
-    pub fn from_file(path: String) -> Self {
-        let values = read(path);
+    /// Reads a grid, along with its layout when the file has a `[regions]` section and the
+    /// constraints declared by its other sections
+    pub fn from_file(path: String) -> Result<Self, SabitaError> {
+        let grid = read_puzzle(path)?;
 
-        Grid::new(values)
+        Grid::with_constraints(grid.values, grid.layout, grid.constraints)
+    }
+
+    /// Every puzzle of a file in the line format, or the single grid of a file in the grid format
+    pub fn collection_from_file(path: String) -> Result<Vec<Self>, SabitaError> {
+        if !is_line_file(path.clone())? {
+            return Ok(vec![Grid::from_file(path)?]);
+        }
+
+        read_collection(path)?.into_iter().map(Grid::new).collect()
+    }
+
+    /// Every conflict of the values of a file under its layout and constraints, none when the
+    /// grid is valid
+    pub fn conflicts_in_file(path: String) -> Result<Vec<ValidationError>, SabitaError> {
+        let grid = read_puzzle(path)?;
+
+        validate_all_with_constraints(&grid.values, &grid.layout, &grid.constraints)
     }
 
     //////////
@@ -124,78 +193,262 @@ impl Grid {
         (*self.values).to_vec()
     }
 
+    /// Solving representation of the grid
+    pub fn board(&self) -> Board {
+        Board::with_layout(&self.values, &self.layout).with_constraints(&self.constraints)
+    }
+
     //////////
     // Methods
 
     pub fn locate_missing_box(&self) -> Vec<BoxLocation> {
-        locate_missing_box(&self.get_values())
+        locate_missing_board_box(&self.board())
     }
 
-    pub fn remove_random_values(&mut self, nb_to_remove: u8) -> Vec<BoxLocation> {
-        let (values, locations) = remove_random_values(&self.values, nb_to_remove);
+    /// Removes values at random, failing when asked to empty every box
+    pub fn remove_random_values<R: Rng>(
+        &mut self,
+        nb_to_remove: u16,
+        generator: &mut R,
+    ) -> Result<Vec<BoxLocation>, SabitaError> {
+        let (values, locations) =
+            remove_random_values_with_layout(&self.values, &self.layout, nb_to_remove, generator)?;
 
         self.values = values;
 
-        locations
+        Ok(locations)
     }
 
     pub fn print(&self) {
         print_2d_vec(&self.get_values());
     }
 
-    pub fn solve(&mut self) {
-        let missing_boxes = self.locate_missing_box();
+    /// Every value on a single line, `.` for blanks
+    pub fn to_line(&self) -> String {
+        values_to_line(&self.values)
+    }
 
-        let values = solve(&self.get_values(), &missing_boxes).unwrap();
+    pub fn solve(&mut self) -> Result<(), SabitaError> {
+        self.values = solve_board(&self.board())?;
 
-        self.values = values;
+        Ok(())
+    }
+
+    /// Solves the grid with the given engine
+    pub fn solve_with(&mut self, solver: &dyn Solver) -> Result<(), SabitaError> {
+        self.values = solver.solve_board(&self.board())?;
+
+        Ok(())
+    }
+
+    /// Counts the solutions of the grid, up to `limit`
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        count_board_solutions(&self.board(), limit)
+    }
+
+    /// Solves the grid step by step with human techniques, without guessing
+    pub fn solve_logically(&self) -> Result<LogicalSolution, SabitaError> {
+        self.expect_standard("Logical solving")?;
+
+        solve_logically(&self.values)
+    }
+
+    /// Rates how hard the grid is for a human
+    pub fn rate(&self) -> Result<Rating, SabitaError> {
+        self.expect_standard("Rating")?;
+
+        rate(&self.values)
+    }
+
+    /// Equivalent grid obtained through the transformation
+    pub fn transform(&self, transformation: &Transformation) -> Result<Grid, SabitaError> {
+        self.expect_standard("Transformation")?;
+
+        Ok(Grid {
+            values: transformation.apply(&self.values),
+            layout: self.layout.clone(),
+            constraints: vec![],
+        })
     }
 
-    pub fn dump_file(&self, path: String) {
-        write(path, self.get_values());
+    /// Smallest equivalent grid through the sudoku symmetries, shared by every variant of a puzzle
+    pub fn canonical(&self) -> Result<Grid, SabitaError> {
+        self.expect_standard("Canonical form")?;
+
+        Ok(Grid {
+            values: canonical_form(&self.values).0,
+            layout: self.layout.clone(),
+            constraints: vec![],
+        })
+    }
+
+    /// Stable fingerprint of the canonical form, to spot duplicated puzzles
+    pub fn fingerprint(&self) -> Result<String, SabitaError> {
+        self.expect_standard("Fingerprint")?;
+
+        Ok(fingerprint(&self.values))
+    }
+
+    /// Lazily iterates over every completion of the grid
+    pub fn solutions(&self) -> impl Iterator<Item = Grid> + '_ {
+        Solutions::from_board(self.board()).map(|values| Grid {
+            values,
+            layout: self.layout.clone(),
+            constraints: self.constraints.clone(),
+        })
+    }
+
+    /// Writes the grid, the regions being written too unless they are the usual ones for its size,
+    /// followed by a section per constraint
+    pub fn dump_file(&self, path: String) -> Result<(), SabitaError> {
+        let mut sections = match self.has_usual_layout() {
+            true => vec![],
+            false => vec![self.layout.to_section()],
+        };
+
+        sections.extend(
+            self.constraints
+                .iter()
+                .map(|constraint| constraint.to_section()),
+        );
+
+        write_with_sections(path, self.get_values(), &sections)
+    }
+
+    /// Writes the grid in the given format, the line format only holding grids of the usual
+    /// layout for their size, without constraints
+    pub fn dump_file_as(&self, path: String, format: Format) -> Result<(), SabitaError> {
+        match format {
+            Format::Grid => self.dump_file(path),
+            Format::Line if self.has_usual_layout() && self.constraints.is_empty() => {
+                write_collection(path, &[self.get_values()])
+            }
+            Format::Line => Err(SabitaError::Unsupported(
+                "The line format only holds grids without regions or constraints".to_string(),
+            )),
+        }
+    }
+
+    /// Checks the regions are the usual ones for the size of the grid
+    fn has_usual_layout(&self) -> bool {
+        Layout::for_size(self.layout.size()).is_some_and(|usual| usual == self.layout)
+    }
+
+    /// Human techniques and symmetries are only known for the classic 9x9 grid, without variant
+    /// constraints
+    fn expect_standard(&self, feature: &str) -> Result<(), SabitaError> {
+        if !self.constraints.is_empty() {
+            return Err(SabitaError::Unsupported(format!(
+                "{feature} only handles standard 9x9 grids, not variants"
+            )));
+        }
+
+        if !self.layout.is_standard() {
+            return Err(SabitaError::Unsupported(format!(
+                "{feature} only handles standard 9x9 grids, not {}",
+                self.layout
+            )));
+        }
+
+        Ok(())
     }
 }
 
 ////////////////////
 
-/// Prints a two dimensions vector to stdout
-pub fn print_2d_vec(grid: &GridValues) {
-    grid.iter().for_each(|line| println!("{:?}", line))
+/// Grid of a puzzle file, its values not checked yet
+fn read_puzzle(path: String) -> Result<Grid, SabitaError> {
+    let (values, sections) = read_with_sections(path)?;
+
+    let layout = match sections
+        .iter()
+        .find(|section| section.name == REGIONS_SECTION)
+    {
+        Some(section) => Layout::from_section(section)?,
+        None => match Layout::for_size(values.len()) {
+            Some(layout) => layout,
+            None => return Err(wrong_number_of_lines(&values)),
+        },
+    };
+
+    let constraints = sections
+        .iter()
+        .filter(|section| section.name != REGIONS_SECTION)
+        .map(|section| match constraint::from_section(section, &layout) {
+            Some(constraint) => Ok(constraint?),
+            None => Err(SabitaError::Parse(format!(
+                "unknown section [{}]",
+                section.name
+            ))),
+        })
+        .collect::<Result<Vec<Arc<dyn Constraint>>, SabitaError>>()?;
+
+    Ok(Grid {
+        values,
+        layout,
+        constraints,
+    })
 }
 
-/// Parse coordinates (line, column) into a region index
-pub fn location_to_region(line: &usize, col: &usize) -> Result<u8, Box<dyn Error>> {
-    let third_of_length = (LENGTH_DIMENSION / 3) as usize;
+/// Checks the values fill the layout, each one in its range
+pub(crate) fn check_dimensions(values: &GridValues, layout: &Layout) -> Result<(), SabitaError> {
+    let size = layout.size();
 
-    for index in 0..LENGTH_DIMENSION {
-        let (start_row, start_column) = region_to_location(&index);
-
-        let end_row = start_row + third_of_length - 1;
-        let end_column = start_column + third_of_length - 1;
+    if values.len() != size {
+        return Err(wrong_number_of_lines(values));
+    }
 
-        let is_in_region_row = line >= &start_row && line <= &end_row;
-        let is_in_region_column = col >= &start_column && col <= &end_column;
+    for (row_index, row) in values.iter().enumerate() {
+        if row.len() != size {
+            return Err(SabitaError::Dimension(format!(
+                "Line index {} has a different number of columns than {}",
+                row_index, size
+            )));
+        }
 
-        if is_in_region_row & is_in_region_column {
-            return Ok(index);
+        for (column_index, value) in row.iter().enumerate() {
+            if *value as usize > size {
+                return Err(SabitaError::ValueOutOfRange {
+                    value: *value,
+                    line: row_index,
+                    column: column_index,
+                });
+            }
         }
     }
 
-    Err(format!("No region found for [{},{}]", line, col).into())
+    Ok(())
+}
+
+pub(crate) fn wrong_number_of_lines(values: &GridValues) -> SabitaError {
+    SabitaError::Dimension(format!("Wrong number of lines: {}", values.len()))
+}
+
+/// Prints a two dimensions vector to stdout
+pub fn print_2d_vec(grid: &GridValues) {
+    grid.iter().for_each(|line| println!("{:?}", line))
+}
+
+/// Parse coordinates (line, column) of a standard grid into a region index
+pub fn location_to_region(line: &usize, col: &usize) -> Result<u8, SabitaError> {
+    let layout = Layout::standard();
+
+    if *line >= layout.size() || *col >= layout.size() {
+        return Err(SabitaError::Dimension(format!(
+            "No region found for [{},{}]",
+            line, col
+        )));
+    }
+
+    Ok(layout.region_of(*line, *col))
 }
 
-/// Parse a region index into coordinates (line, column)
-pub fn region_to_location(region_index: &u8) -> (usize, usize) {
-    match region_index {
-        0 => (0_usize, 0_usize),
-        1 => (0_usize, 3_usize),
-        2 => (0_usize, 6_usize),
-        3 => (3_usize, 0_usize),
-        4 => (3_usize, 3_usize),
-        5 => (3_usize, 6_usize),
-        6 => (6_usize, 0_usize),
-        7 => (6_usize, 3_usize),
-        8 => (6_usize, 6_usize),
-        _ => panic!("Region out of range"),
+/// Parse a region index of a standard grid into the coordinates (line, column) of its first box
+pub fn region_to_location(region_index: &u8) -> Result<(usize, usize), SabitaError> {
+    match Layout::standard().region_boxes(*region_index).first() {
+        Some(location) => Ok(*location),
+        None => Err(SabitaError::Dimension(format!(
+            "Region '{region_index}' out of range"
+        ))),
     }
 }
@@ -0,0 +1,261 @@
+use super::{
+    board::{all_candidates, pop_lowest, value_to_mask, Board, Candidates},
+    constants::TO_BE_SOLVED,
+    constraint::{cell_to_string, parse_cell, Constraint, InvalidConstraint},
+    file::Section,
+    layout::Layout,
+};
+
+////////////////////////////////////////
+
+/// Name of the file section listing the cages of a killer sudoku
+pub const KILLER_SECTION: &str = "killer";
+
+////////////////////////////////////////
+
+/// Group of boxes whose values add up to `sum`, without repeating any of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cage {
+    /// Flat indices of the boxes, line by line
+    cells: Vec<usize>,
+    sum: u32,
+}
+
+impl Cage {
+    pub fn new(cells: Vec<usize>, sum: u32) -> Self {
+        Cage { cells, sum }
+    }
+
+    pub fn cells(&self) -> &[usize] {
+        &self.cells
+    }
+
+    pub fn sum(&self) -> u32 {
+        self.sum
+    }
+}
+
+//////////
+
+/// Killer sudoku: boxes grouped into cages, each one adding up to its sum with distinct values
+/// Candidates of a box are pruned to the values found in at least one combination of the values
+/// left that completes the sum of its cage
+#[derive(Debug, Clone)]
+pub struct Killer {
+    size: usize,
+    cages: Vec<Cage>,
+    /// Position in `cages` of the cage holding each box, if any
+    cage_of: Vec<Option<usize>>,
+}
+
+////////////////////
+
+impl Killer {
+    //////////
+    // Constructors
+
+    /// Checks every box is inside the grid and in one cage at most, and that every sum can be
+    /// reached with distinct values
+    pub fn new(layout: &Layout, cages: Vec<Cage>) -> Result<Self, InvalidConstraint> {
+        let size = layout.size();
+        let mut cage_of = vec![None; layout.nb_boxes()];
+
+        for (position, cage) in cages.iter().enumerate() {
+            if cage.cells.is_empty() || cage.cells.len() > size {
+                return Err(InvalidConstraint::new(format!(
+                    "cage {position} holds {} boxes",
+                    cage.cells.len()
+                )));
+            }
+
+            for cell in cage.cells.iter().copied() {
+                match cage_of.get(cell) {
+                    Some(None) => cage_of[cell] = Some(position),
+                    Some(Some(_)) => {
+                        return Err(InvalidConstraint::new(format!(
+                            "box [{}:{}] is in more than one cage",
+                            cell / size,
+                            cell % size
+                        )))
+                    }
+                    None => {
+                        return Err(InvalidConstraint::new(format!(
+                            "box {cell} of cage {position} is out of the grid"
+                        )))
+                    }
+                }
+            }
+
+            if combinations(all_candidates(size), cage.cells.len(), cage.sum).is_none() {
+                return Err(InvalidConstraint::new(format!(
+                    "{} distinct values can not add up to {}",
+                    cage.cells.len(),
+                    cage.sum
+                )));
+            }
+        }
+
+        Ok(Killer {
+            size,
+            cages,
+            cage_of,
+        })
+    }
+
+    /// Cages given by a `[killer]` section, one per line: the sum, then the `line:column` of its
+    /// boxes, such as `15 0:0 0:1 1:0`
+    pub fn from_section(section: &Section, layout: &Layout) -> Result<Self, InvalidConstraint> {
+        let mut cages = vec![];
+
+        for line in section.lines.iter() {
+            let invalid = || InvalidConstraint::new(format!("wrong cage '{line}'"));
+
+            let mut words = line.split_whitespace();
+
+            let sum = words
+                .next()
+                .and_then(|word| word.parse::<u32>().ok())
+                .ok_or_else(invalid)?;
+
+            let cells = words
+                .map(|word| parse_cell(word, layout))
+                .collect::<Option<Vec<usize>>>()
+                .ok_or_else(invalid)?;
+
+            cages.push(Cage::new(cells, sum));
+        }
+
+        Killer::new(layout, cages)
+    }
+
+    //////////
+    // Accessors
+
+    pub fn cages(&self) -> &[Cage] {
+        &self.cages
+    }
+}
+
+impl Constraint for Killer {
+    fn name(&self) -> &str {
+        KILLER_SECTION
+    }
+
+    fn prune(&self, board: &Board, index: usize, candidates: Candidates) -> Candidates {
+        let Some(cage) = self.cage_of[index].map(|position| &self.cages[position]) else {
+            return candidates;
+        };
+
+        let mut placed = 0;
+        let mut placed_sum = 0;
+        let mut nb_empty = 1;
+
+        for cell in cage.cells.iter().copied().filter(|cell| *cell != index) {
+            match board.get(cell) {
+                TO_BE_SOLVED => nb_empty += 1,
+                value => {
+                    placed |= value_to_mask(value);
+                    placed_sum += u32::from(value);
+                }
+            }
+        }
+
+        if placed_sum >= cage.sum {
+            return 0;
+        }
+
+        let pool = board.all_candidates() & !placed;
+
+        match combinations(pool, nb_empty, cage.sum - placed_sum) {
+            Some(used) => candidates & used,
+            None => 0,
+        }
+    }
+
+    /// Filled boxes of the cage, its sum involving all of them
+    fn conflicting_boxes(&self, board: &Board, index: usize, _value: u8) -> Vec<usize> {
+        let Some(cage) = self.cage_of[index].map(|position| &self.cages[position]) else {
+            return vec![];
+        };
+
+        cage.cells
+            .iter()
+            .copied()
+            .filter(|cell| *cell != index && board.get(*cell) != TO_BE_SOLVED)
+            .collect()
+    }
+
+    fn to_section(&self) -> Section {
+        let lines = self
+            .cages
+            .iter()
+            .map(|cage| {
+                let cells: Vec<String> = cage
+                    .cells
+                    .iter()
+                    .map(|cell| cell_to_string(*cell, self.size))
+                    .collect();
+
+                format!("{} {}", cage.sum, cells.join(" "))
+            })
+            .collect();
+
+        Section {
+            name: self.name().to_string(),
+            lines,
+        }
+    }
+}
+
+////////////////////
+
+/// Every value used by at least one set of `count` distinct values of the pool adding up to
+/// `target`, None if there is no such set
+pub fn combinations(pool: Candidates, count: usize, target: u32) -> Option<Candidates> {
+    if count == 0 {
+        return (target == 0).then_some(0);
+    }
+
+    let (smallest, largest) = sum_bounds(pool, count)?;
+
+    if target < smallest || target > largest {
+        return None;
+    }
+
+    let mut used = None;
+    let mut rest = pool;
+
+    while let Some(value) = pop_lowest(&mut rest) {
+        if u32::from(value) > target {
+            break;
+        }
+
+        if let Some(others) = combinations(rest, count - 1, target - u32::from(value)) {
+            used = Some(used.unwrap_or(0) | others | value_to_mask(value));
+        }
+    }
+
+    used
+}
+
+/// Sums of the `count` smallest and of the `count` largest values of the pool, None if it holds
+/// fewer values
+fn sum_bounds(pool: Candidates, count: usize) -> Option<(u32, u32)> {
+    if (pool.count_ones() as usize) < count {
+        return None;
+    }
+
+    let mut lowest = pool;
+    let mut highest = pool;
+    let (mut smallest, mut largest) = (0, 0);
+
+    for _ in 0..count {
+        smallest += u32::from(pop_lowest(&mut lowest)?);
+
+        let value = Candidates::BITS - highest.leading_zeros();
+        highest &= !value_to_mask(value as u8);
+        largest += value;
+    }
+
+    Some((smallest, largest))
+}
@@ -0,0 +1,278 @@
+use super::{
+    constants::{LENGTH_DIMENSION, MAX_LENGTH_DIMENSION},
+    file::{parse_rows, rows_to_lines, Section},
+};
+
+use std::fmt;
+
+////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct InvalidLayout {
+    reason: String,
+}
+
+impl InvalidLayout {
+    pub(crate) fn new(reason: String) -> Self {
+        InvalidLayout { reason }
+    }
+}
+
+impl fmt::Display for InvalidLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid layout: {}", self.reason)
+    }
+}
+
+//////////
+
+/// Name of the file section giving the region of each box
+pub const REGIONS_SECTION: &str = "regions";
+
+////////////////////////////////////////
+
+/// Size of a grid and the way its boxes are grouped into regions
+/// A grid of size `n` holds the values 1 to `n` and is split into `n` regions of `n` boxes, either
+/// rectangles of `box_height` lines by `box_width` columns, or any connected shapes for jigsaw
+/// sudokus
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Layout {
+    size: usize,
+    /// Height and width of the rectangular regions, None for jigsaw ones
+    box_shape: Option<(usize, usize)>,
+    /// Region of each box, read line by line
+    regions: Vec<u8>,
+}
+
+////////////////////
+
+impl Layout {
+    //////////
+    // Constructors
+
+    pub fn new(box_height: usize, box_width: usize) -> Result<Self, InvalidLayout> {
+        let size = box_height * box_width;
+
+        if box_height == 0 || box_width == 0 || size > MAX_LENGTH_DIMENSION as usize {
+            return Err(InvalidLayout {
+                reason: format!(
+                    "boxes of {box_height}x{box_width} do not make a grid of {MAX_LENGTH_DIMENSION} values or less"
+                ),
+            });
+        }
+
+        let regions_per_line = size / box_width;
+
+        let regions = (0..size * size)
+            .map(|index| {
+                let (line, column) = (index / size, index % size);
+
+                ((line / box_height) * regions_per_line + column / box_width) as u8
+            })
+            .collect();
+
+        Ok(Layout {
+            size,
+            box_shape: Some((box_height, box_width)),
+            regions,
+        })
+    }
+
+    /// Jigsaw layout, `regions[line][column]` being the region of each box, from 0
+    /// Each region must hold as many boxes as the grid has lines, all of them connected
+    pub fn jigsaw(regions: &[Vec<u8>]) -> Result<Self, InvalidLayout> {
+        let size = regions.len();
+
+        let invalid = |reason: String| Err(InvalidLayout { reason });
+
+        if size == 0 || size > MAX_LENGTH_DIMENSION as usize {
+            return invalid(format!("{size} lines of regions"));
+        }
+
+        if let Some(line) = regions.iter().position(|row| row.len() != size) {
+            return invalid(format!(
+                "line {line} of the regions is not {size} boxes long"
+            ));
+        }
+
+        let flat: Vec<u8> = regions.iter().flatten().copied().collect();
+
+        for region in 0..size as u8 {
+            let nb_boxes = flat.iter().filter(|id| **id == region).count();
+
+            if nb_boxes != size {
+                return invalid(format!(
+                    "region {region} has {nb_boxes} boxes instead of {size}"
+                ));
+            }
+        }
+
+        if let Some(id) = flat.iter().find(|id| **id as usize >= size) {
+            return invalid(format!("region {id} out of bound"));
+        }
+
+        let layout = Layout {
+            size,
+            box_shape: None,
+            regions: flat,
+        };
+
+        for region in 0..size as u8 {
+            if !layout.is_connected(region) {
+                return invalid(format!("region {region} is not connected"));
+            }
+        }
+
+        Ok(layout)
+    }
+
+    /// Classic 9x9 grid of 3x3 boxes
+    pub fn standard() -> Self {
+        let third = (LENGTH_DIMENSION / 3) as usize;
+
+        Layout::new(third, third).unwrap()
+    }
+
+    /// Usual layout of a grid of the given size: 2x2 boxes for 4x4, 2x3 for 6x6, 3x3 for 9x9,
+    /// 3x4 for 12x12, 4x4 for 16x16 and 5x5 for 25x25
+    pub fn for_size(size: usize) -> Option<Self> {
+        let (box_height, box_width) = match size {
+            4 => (2, 2),
+            6 => (2, 3),
+            9 => (3, 3),
+            12 => (3, 4),
+            16 => (4, 4),
+            25 => (5, 5),
+            _ => return None,
+        };
+
+        Layout::new(box_height, box_width).ok()
+    }
+
+    /// Rectangular layout if the regions form one, jigsaw layout otherwise
+    pub fn from_regions(regions: &[Vec<u8>]) -> Result<Self, InvalidLayout> {
+        let jigsaw = Layout::jigsaw(regions)?;
+
+        let rectangular = (1..=jigsaw.size)
+            .filter(|height| jigsaw.size % height == 0)
+            .filter_map(|height| Layout::new(height, jigsaw.size / height).ok())
+            .find(|layout| layout.regions == jigsaw.regions);
+
+        Ok(rectangular.unwrap_or(jigsaw))
+    }
+
+    /// Layout given by a `[regions]` section of a file
+    pub fn from_section(section: &Section) -> Result<Self, InvalidLayout> {
+        let rows = parse_rows(section).map_err(|err| InvalidLayout::new(err.to_string()))?;
+
+        Layout::from_regions(&rows)
+    }
+
+    //////////
+    // Accessors
+
+    /// Number of lines, columns, regions and values
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn nb_boxes(&self) -> usize {
+        self.size * self.size
+    }
+
+    /// Height and width of the regions, None for a jigsaw layout
+    pub fn box_shape(&self) -> Option<(usize, usize)> {
+        self.box_shape
+    }
+
+    pub fn is_jigsaw(&self) -> bool {
+        self.box_shape.is_none()
+    }
+
+    pub fn is_standard(&self) -> bool {
+        *self == Layout::standard()
+    }
+
+    //////////
+    // Methods
+
+    pub fn region_of(&self, line: usize, column: usize) -> u8 {
+        self.regions[line * self.size + column]
+    }
+
+    /// Region of a box given by its flat index
+    pub fn region_of_index(&self, index: usize) -> u8 {
+        self.regions[index]
+    }
+
+    /// Coordinates (line, column) of the boxes of a region, read line by line
+    pub fn region_boxes(&self, region: u8) -> Vec<(usize, usize)> {
+        (0..self.nb_boxes())
+            .filter(|index| self.regions[*index] == region)
+            .map(|index| (index / self.size, index % self.size))
+            .collect()
+    }
+
+    /// Region of each box, line by line
+    pub fn to_rows(&self) -> Vec<Vec<u8>> {
+        self.regions
+            .chunks(self.size)
+            .map(|line| line.to_vec())
+            .collect()
+    }
+
+    /// `[regions]` section of a file describing the layout
+    pub fn to_section(&self) -> Section {
+        Section {
+            name: REGIONS_SECTION.to_string(),
+            lines: rows_to_lines(&self.to_rows()),
+        }
+    }
+
+    /// Checks every box of the region can be reached from the others through its neighbours
+    fn is_connected(&self, region: u8) -> bool {
+        let boxes = self.region_boxes(region);
+
+        let Some(start) = boxes.first() else {
+            return false;
+        };
+
+        let mut reached = vec![*start];
+        let mut to_visit = vec![*start];
+
+        while let Some((line, column)) = to_visit.pop() {
+            let neighbours = [
+                (line.wrapping_sub(1), column),
+                (line + 1, column),
+                (line, column.wrapping_sub(1)),
+                (line, column + 1),
+            ];
+
+            for neighbour in neighbours {
+                if boxes.contains(&neighbour) && !reached.contains(&neighbour) {
+                    reached.push(neighbour);
+                    to_visit.push(neighbour);
+                }
+            }
+        }
+
+        reached.len() == boxes.len()
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout::standard()
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.box_shape {
+            Some((box_height, box_width)) => {
+                write!(f, "{0}x{0} ({box_height}x{box_width} boxes)", self.size)
+            }
+            None => write!(f, "{0}x{0} (jigsaw)", self.size),
+        }
+    }
+}
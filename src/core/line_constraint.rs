@@ -0,0 +1,338 @@
+use super::{
+    board::{value_to_mask, Board, Candidates},
+    constants::TO_BE_SOLVED,
+    constraint::{cell_to_string, pairwise_conflicts, parse_cell, Constraint, InvalidConstraint},
+    file::Section,
+    layout::Layout,
+};
+
+////////////////////////////////////////
+
+const THERMOMETER_SECTION: &str = "thermo";
+const ARROW_SECTION: &str = "arrow";
+const PALINDROME_SECTION: &str = "palindrome";
+const WHISPERS_SECTION: &str = "whispers";
+
+/// Smallest difference between neighbours on a German whispers line
+const WHISPERS_DIFFERENCE: i32 = 5;
+
+////////////////////////////////////////
+
+/// Rule followed by the boxes along a line drawn over the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    /// Values strictly increase from the bulb, the first box
+    Thermometer,
+    /// The circle, the first box, equals the sum of the boxes along the arrow
+    Arrow,
+    /// Values read the same from both ends
+    Palindrome,
+    /// German whispers: neighbours along the line differ by 5 or more
+    Whispers,
+}
+
+impl LineKind {
+    /// Kind declared by a file section of that name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            THERMOMETER_SECTION => Some(LineKind::Thermometer),
+            ARROW_SECTION => Some(LineKind::Arrow),
+            PALINDROME_SECTION => Some(LineKind::Palindrome),
+            WHISPERS_SECTION => Some(LineKind::Whispers),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LineKind::Thermometer => THERMOMETER_SECTION,
+            LineKind::Arrow => ARROW_SECTION,
+            LineKind::Palindrome => PALINDROME_SECTION,
+            LineKind::Whispers => WHISPERS_SECTION,
+        }
+    }
+}
+
+//////////
+
+/// Lines of one kind drawn over the grid, each one given by its boxes in order
+#[derive(Debug, Clone)]
+pub struct LineConstraint {
+    kind: LineKind,
+    size: usize,
+    /// Flat indices of the boxes of each line, from its start
+    lines: Vec<Vec<usize>>,
+}
+
+////////////////////
+
+impl LineConstraint {
+    //////////
+    // Constructors
+
+    /// Checks every line has 2 boxes or more, each one inside the grid, touching the previous one,
+    /// even by a corner, and not used twice
+    pub fn new(
+        kind: LineKind,
+        layout: &Layout,
+        lines: Vec<Vec<usize>>,
+    ) -> Result<Self, InvalidConstraint> {
+        let size = layout.size();
+
+        for (position, cells) in lines.iter().enumerate() {
+            let invalid = |reason: &str| {
+                Err(InvalidConstraint::new(format!(
+                    "{} {position} {reason}",
+                    kind.name()
+                )))
+            };
+
+            if cells.len() < 2 {
+                return invalid("needs 2 boxes or more");
+            }
+
+            if kind == LineKind::Thermometer && cells.len() > size {
+                return invalid("is longer than the number of values");
+            }
+
+            if cells.iter().any(|cell| *cell >= layout.nb_boxes()) {
+                return invalid("goes out of the grid");
+            }
+
+            for (index, cell) in cells.iter().enumerate() {
+                if cells[..index].contains(cell) {
+                    return invalid("goes through a box twice");
+                }
+            }
+
+            if cells
+                .windows(2)
+                .any(|pair| !are_touching(pair[0], pair[1], size))
+            {
+                return invalid("has boxes that do not touch");
+            }
+        }
+
+        Ok(LineConstraint { kind, size, lines })
+    }
+
+    /// Lines given by a section named after their kind, one per line of the section: the
+    /// `line:column` of its boxes in order, such as `0:0 0:1 1:2`
+    pub fn from_section(section: &Section, layout: &Layout) -> Result<Self, InvalidConstraint> {
+        let Some(kind) = LineKind::from_name(&section.name) else {
+            return Err(InvalidConstraint::new(format!(
+                "[{}] is not a line section",
+                section.name
+            )));
+        };
+
+        let lines = section
+            .lines
+            .iter()
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|word| parse_cell(word, layout))
+                    .collect::<Option<Vec<usize>>>()
+                    .ok_or_else(|| {
+                        InvalidConstraint::new(format!("wrong {} '{line}'", kind.name()))
+                    })
+            })
+            .collect::<Result<Vec<Vec<usize>>, InvalidConstraint>>()?;
+
+        LineConstraint::new(kind, layout, lines)
+    }
+
+    //////////
+    // Accessors
+
+    pub fn kind(&self) -> LineKind {
+        self.kind
+    }
+
+    pub fn lines(&self) -> &[Vec<usize>] {
+        &self.lines
+    }
+
+    //////////
+    // Methods
+
+    /// Values left to the box at `position` on a thermometer
+    fn prune_thermometer(&self, board: &Board, cells: &[usize], position: usize) -> Candidates {
+        let position = position as i32;
+        let mut low = position + 1;
+        let mut high = self.size as i32 - (cells.len() as i32 - 1 - position);
+
+        for (other, cell) in cells.iter().enumerate() {
+            let other = other as i32;
+
+            match board.get(*cell) {
+                TO_BE_SOLVED => {}
+                _ if other == position => {}
+                value if other < position => low = low.max(i32::from(value) + position - other),
+                value => high = high.min(i32::from(value) - (other - position)),
+            }
+        }
+
+        range_mask(low, high)
+    }
+
+    /// Values left to the box at `position` on an arrow, its circle at position 0
+    fn prune_arrow(&self, board: &Board, cells: &[usize], position: usize) -> Candidates {
+        let size = self.size as i32;
+        let circle = match board.get(cells[0]) {
+            TO_BE_SOLVED => None,
+            value => Some(i32::from(value)),
+        };
+
+        let (mut placed_sum, mut nb_empty) = (0, 0);
+
+        for cell in cells
+            .iter()
+            .skip(1)
+            .filter(|cell| **cell != cells[position])
+        {
+            match board.get(*cell) {
+                TO_BE_SOLVED => nb_empty += 1,
+                value => placed_sum += i32::from(value),
+            }
+        }
+
+        match (position, circle) {
+            (0, _) => range_mask(placed_sum + nb_empty, placed_sum + nb_empty * size),
+            (_, Some(circle)) => range_mask(
+                circle - placed_sum - nb_empty * size,
+                circle - placed_sum - nb_empty,
+            ),
+            (_, None) => range_mask(1, size - placed_sum - nb_empty),
+        }
+    }
+
+    /// Values left to the box at `position` on a palindrome
+    fn prune_palindrome(&self, board: &Board, cells: &[usize], position: usize) -> Candidates {
+        let mirror = cells[cells.len() - 1 - position];
+
+        match board.get(mirror) {
+            _ if mirror == cells[position] => range_mask(1, self.size as i32),
+            TO_BE_SOLVED => range_mask(1, self.size as i32),
+            value => value_to_mask(value),
+        }
+    }
+
+    /// Values left to the box at `position` on a German whispers line
+    fn prune_whispers(&self, board: &Board, cells: &[usize], position: usize) -> Candidates {
+        let size = self.size as i32;
+
+        // A value too close to every other one can not have any neighbour
+        let mut allowed =
+            range_mask(1, size - WHISPERS_DIFFERENCE) | range_mask(1 + WHISPERS_DIFFERENCE, size);
+
+        let neighbours = [position.checked_sub(1), Some(position + 1)];
+
+        for neighbour in neighbours.into_iter().flatten() {
+            let Some(cell) = cells.get(neighbour) else {
+                continue;
+            };
+
+            match board.get(*cell) {
+                TO_BE_SOLVED => {}
+                value => {
+                    let value = i32::from(value);
+
+                    allowed &= range_mask(1, value - WHISPERS_DIFFERENCE)
+                        | range_mask(value + WHISPERS_DIFFERENCE, size);
+                }
+            }
+        }
+
+        allowed
+    }
+}
+
+impl Constraint for LineConstraint {
+    fn name(&self) -> &str {
+        self.kind.name()
+    }
+
+    fn prune(&self, board: &Board, index: usize, candidates: Candidates) -> Candidates {
+        let mut candidates = candidates;
+
+        for cells in self.lines.iter() {
+            let Some(position) = cells.iter().position(|cell| *cell == index) else {
+                continue;
+            };
+
+            candidates &= match self.kind {
+                LineKind::Thermometer => self.prune_thermometer(board, cells, position),
+                LineKind::Arrow => self.prune_arrow(board, cells, position),
+                LineKind::Palindrome => self.prune_palindrome(board, cells, position),
+                LineKind::Whispers => self.prune_whispers(board, cells, position),
+            };
+        }
+
+        candidates
+    }
+
+    /// Filled boxes of the arrows through the box, their sum involving all of them, or the boxes
+    /// of the other lines clashing with it
+    fn conflicting_boxes(&self, board: &Board, index: usize, value: u8) -> Vec<usize> {
+        if self.kind != LineKind::Arrow {
+            return pairwise_conflicts(self, board, index, value);
+        }
+
+        let mut boxes: Vec<usize> = self
+            .lines
+            .iter()
+            .filter(|cells| cells.contains(&index))
+            .flatten()
+            .copied()
+            .filter(|cell| *cell != index && board.get(*cell) != TO_BE_SOLVED)
+            .collect();
+
+        boxes.sort();
+        boxes.dedup();
+
+        boxes
+    }
+
+    fn to_section(&self) -> Section {
+        Section {
+            name: self.name().to_string(),
+            lines: self
+                .lines
+                .iter()
+                .map(|cells| {
+                    cells
+                        .iter()
+                        .map(|cell| cell_to_string(*cell, self.size))
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                })
+                .collect(),
+        }
+    }
+}
+
+////////////////////
+
+/// Mask of the values from `low` to `high`, both included, empty when `low` is above `high`
+fn range_mask(low: i32, high: i32) -> Candidates {
+    let low = low.max(1);
+    let high = high.min(Candidates::BITS as i32);
+
+    if low > high {
+        return 0;
+    }
+
+    let up_to_high = ((1_u64 << high) - 1) as Candidates;
+    let below_low = ((1_u64 << (low - 1)) - 1) as Candidates;
+
+    up_to_high & !below_low
+}
+
+/// Checks two boxes share a side or a corner
+fn are_touching(cell: usize, other: usize, size: usize) -> bool {
+    let (line, column) = (cell / size, cell % size);
+    let (other_line, other_column) = (other / size, other % size);
+
+    line.abs_diff(other_line) <= 1 && column.abs_diff(other_column) <= 1 && cell != other
+}
@@ -0,0 +1,841 @@
+use super::{
+    board::{
+        index_to_coordinates, index_to_location, location_to_index, mask_to_values, value_to_mask,
+        Candidates,
+    },
+    constants::{LENGTH_DIMENSION, MAX_NB_VALUES, TO_BE_SOLVED},
+    error::SabitaError,
+    grid::{check_dimensions, BoxLocation, GridValues},
+    layout::Layout,
+    solver::{get_solutions_complexity_sorted, locate_missing_box},
+};
+
+use std::fmt;
+
+////////////////////////////////////////
+
+const LENGTH: usize = LENGTH_DIMENSION as usize;
+const NB_BOXES: usize = MAX_NB_VALUES as usize;
+
+/// Named solving techniques, ordered from the easiest to the hardest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Technique {
+    NakedSingle,
+    HiddenSingle,
+    PointingPair,
+    BoxLineReduction,
+    NakedPair,
+    HiddenPair,
+    NakedTriple,
+    HiddenTriple,
+    XWing,
+    SimpleColoring,
+    XYWing,
+    Swordfish,
+    XYZWing,
+    NakedQuad,
+    HiddenQuad,
+    Jellyfish,
+}
+
+impl Technique {
+    pub const ALL: [Technique; 16] = [
+        Technique::NakedSingle,
+        Technique::HiddenSingle,
+        Technique::PointingPair,
+        Technique::BoxLineReduction,
+        Technique::NakedPair,
+        Technique::HiddenPair,
+        Technique::NakedTriple,
+        Technique::HiddenTriple,
+        Technique::XWing,
+        Technique::SimpleColoring,
+        Technique::XYWing,
+        Technique::Swordfish,
+        Technique::XYZWing,
+        Technique::NakedQuad,
+        Technique::HiddenQuad,
+        Technique::Jellyfish,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Technique::NakedSingle => "Naked single",
+            Technique::HiddenSingle => "Hidden single",
+            Technique::PointingPair => "Pointing pair",
+            Technique::BoxLineReduction => "Box/line reduction",
+            Technique::NakedPair => "Naked pair",
+            Technique::HiddenPair => "Hidden pair",
+            Technique::NakedTriple => "Naked triple",
+            Technique::HiddenTriple => "Hidden triple",
+            Technique::XWing => "X-Wing",
+            Technique::SimpleColoring => "Simple coloring",
+            Technique::XYWing => "XY-Wing",
+            Technique::Swordfish => "Swordfish",
+            Technique::XYZWing => "XYZ-Wing",
+            Technique::NakedQuad => "Naked quad",
+            Technique::HiddenQuad => "Hidden quad",
+            Technique::Jellyfish => "Jellyfish",
+        }
+    }
+
+    /// Score of the technique on a scale growing with the order of the techniques
+    pub fn difficulty(&self) -> f32 {
+        match self {
+            Technique::NakedSingle => 1.0,
+            Technique::HiddenSingle => 1.2,
+            Technique::PointingPair => 2.0,
+            Technique::BoxLineReduction => 2.2,
+            Technique::NakedPair => 3.0,
+            Technique::HiddenPair => 3.2,
+            Technique::NakedTriple => 3.6,
+            Technique::HiddenTriple => 3.8,
+            Technique::XWing => 4.2,
+            Technique::SimpleColoring => 4.4,
+            Technique::XYWing => 4.6,
+            Technique::Swordfish => 5.0,
+            Technique::XYZWing => 5.2,
+            Technique::NakedQuad => 5.6,
+            Technique::HiddenQuad => 5.8,
+            Technique::Jellyfish => 6.2,
+        }
+    }
+}
+
+impl fmt::Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+//////////
+
+/// One deduction: the technique used, the boxes forming the pattern, and what it allowed to
+/// place or to remove from the candidates
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub technique: Technique,
+    pub cells: Vec<BoxLocation>,
+    pub placement: Option<(BoxLocation, u8)>,
+    pub eliminations: Vec<(BoxLocation, u8)>,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells = self
+            .cells
+            .iter()
+            .map(|cell| cell.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        write!(f, "{} on {cells}", self.technique)?;
+
+        if let Some((location, value)) = &self.placement {
+            write!(f, ": {location} = {value}")?;
+        }
+
+        if !self.eliminations.is_empty() {
+            let eliminations = self
+                .eliminations
+                .iter()
+                .map(|(location, value)| format!("{location} <> {value}"))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            write!(f, ": {eliminations}")?;
+        }
+
+        Ok(())
+    }
+}
+
+//////////
+
+#[derive(Debug, Clone)]
+pub struct LogicalSolution {
+    /// Grid as far as the techniques could take it
+    pub values: GridValues,
+    pub steps: Vec<Step>,
+}
+
+impl LogicalSolution {
+    pub fn is_solved(&self) -> bool {
+        self.values.iter().flatten().all(|&val| val != TO_BE_SOLVED)
+    }
+
+    /// Hardest technique the resolution needed, None if no step was required
+    pub fn hardest_technique(&self) -> Option<Technique> {
+        self.steps.iter().map(|step| step.technique).max()
+    }
+}
+
+////////////////////////////////////////
+
+/// Solves the grid the way a human would, applying the easiest technique that makes progress and
+/// starting over from the easiest one after each step
+/// Stops when the grid is solved or when no known technique applies anymore
+/// Fails when the grid is not a standard 9x9 one
+pub fn solve_logically(values: &GridValues) -> Result<LogicalSolution, SabitaError> {
+    check_dimensions(values, &Layout::standard())?;
+
+    let mut state = LogicState::new(values)?;
+    let mut steps = vec![];
+
+    while !state.is_solved() {
+        let step = Technique::ALL
+            .iter()
+            .find_map(|technique| state.find(*technique));
+
+        match step {
+            Some(step) => {
+                state.apply(&step);
+                steps.push(step);
+            }
+            None => break,
+        }
+    }
+
+    Ok(LogicalSolution {
+        values: state.get_values(),
+        steps,
+    })
+}
+
+////////////////////
+
+/// Values and pencil marks of a grid being solved by hand
+struct LogicState {
+    values: [u8; NB_BOXES],
+    candidates: [Candidates; NB_BOXES],
+    /// Lines, then columns, then regions
+    houses: Vec<Vec<usize>>,
+}
+
+impl LogicState {
+    fn new(grid_values: &GridValues) -> Result<Self, SabitaError> {
+        let mut values = [TO_BE_SOLVED; NB_BOXES];
+        let mut candidates = [0; NB_BOXES];
+
+        for (line, row) in grid_values.iter().enumerate() {
+            for (column, value) in row.iter().enumerate() {
+                values[line * LENGTH + column] = *value;
+            }
+        }
+
+        let missing_boxes = locate_missing_box(grid_values)?;
+
+        for sorted in get_solutions_complexity_sorted(grid_values, &missing_boxes)? {
+            candidates[location_to_index(sorted.location)] = sorted
+                .solutions
+                .iter()
+                .fold(0, |mask, value| mask | value_to_mask(*value));
+        }
+
+        let mut houses = vec![vec![]; LENGTH * 3];
+
+        for index in 0..NB_BOXES {
+            let (line, column, region) = index_to_coordinates(index);
+
+            houses[line].push(index);
+            houses[LENGTH + column].push(index);
+            houses[LENGTH * 2 + region].push(index);
+        }
+
+        Ok(LogicState {
+            values,
+            candidates,
+            houses,
+        })
+    }
+
+    fn get_values(&self) -> GridValues {
+        self.values
+            .chunks(LENGTH)
+            .map(|line| line.to_vec())
+            .collect()
+    }
+
+    fn is_solved(&self) -> bool {
+        self.values.iter().all(|&val| val != TO_BE_SOLVED)
+    }
+
+    fn apply(&mut self, step: &Step) {
+        if let Some((location, value)) = &step.placement {
+            let index = location_to_index(location);
+
+            self.values[index] = *value;
+            self.candidates[index] = 0;
+
+            for other in 0..NB_BOXES {
+                if sees(index, other) {
+                    self.candidates[other] &= !value_to_mask(*value);
+                }
+            }
+        }
+
+        for (location, value) in step.eliminations.iter() {
+            self.candidates[location_to_index(location)] &= !value_to_mask(*value);
+        }
+    }
+
+    fn find(&self, technique: Technique) -> Option<Step> {
+        match technique {
+            Technique::NakedSingle => self.naked_single(),
+            Technique::HiddenSingle => self.hidden_single(),
+            Technique::PointingPair => self.pointing_pair(),
+            Technique::BoxLineReduction => self.box_line_reduction(),
+            Technique::NakedPair => self.naked_subset(technique, 2),
+            Technique::NakedTriple => self.naked_subset(technique, 3),
+            Technique::NakedQuad => self.naked_subset(technique, 4),
+            Technique::HiddenPair => self.hidden_subset(technique, 2),
+            Technique::HiddenTriple => self.hidden_subset(technique, 3),
+            Technique::HiddenQuad => self.hidden_subset(technique, 4),
+            Technique::XWing => self.fish(technique, 2),
+            Technique::Swordfish => self.fish(technique, 3),
+            Technique::Jellyfish => self.fish(technique, 4),
+            Technique::XYWing => self.xy_wing(),
+            Technique::XYZWing => self.xyz_wing(),
+            Technique::SimpleColoring => self.simple_coloring(),
+        }
+    }
+
+    //////////
+    // Helpers
+
+    fn has_candidate(&self, index: usize, value: u8) -> bool {
+        self.candidates[index] & value_to_mask(value) != 0
+    }
+
+    /// Empty boxes of the house where the value can still go
+    fn positions(&self, house: &[usize], value: u8) -> Vec<usize> {
+        house
+            .iter()
+            .copied()
+            .filter(|&index| self.has_candidate(index, value))
+            .collect()
+    }
+
+    fn step(
+        technique: Technique,
+        cells: &[usize],
+        placement: Option<(usize, u8)>,
+        eliminations: Vec<(usize, u8)>,
+    ) -> Step {
+        Step {
+            technique,
+            cells: cells
+                .iter()
+                .map(|&index| index_to_location(index))
+                .collect(),
+            placement: placement.map(|(index, value)| (index_to_location(index), value)),
+            eliminations: eliminations
+                .into_iter()
+                .map(|(index, value)| (index_to_location(index), value))
+                .collect(),
+        }
+    }
+
+    /// Removals that would actually change the candidates, None if there is none
+    fn eliminations_step(
+        &self,
+        technique: Technique,
+        cells: &[usize],
+        targets: impl Iterator<Item = (usize, u8)>,
+    ) -> Option<Step> {
+        let mut eliminations: Vec<(usize, u8)> = targets
+            .filter(|&(index, value)| self.has_candidate(index, value))
+            .collect();
+
+        eliminations.sort();
+        eliminations.dedup();
+
+        if eliminations.is_empty() {
+            None
+        } else {
+            Some(Self::step(technique, cells, None, eliminations))
+        }
+    }
+
+    //////////
+    // Singles
+
+    fn naked_single(&self) -> Option<Step> {
+        (0..NB_BOXES)
+            .find(|&index| self.candidates[index].count_ones() == 1)
+            .map(|index| {
+                let value = self.candidates[index].trailing_zeros() as u8 + 1;
+
+                Self::step(
+                    Technique::NakedSingle,
+                    &[index],
+                    Some((index, value)),
+                    vec![],
+                )
+            })
+    }
+
+    fn hidden_single(&self) -> Option<Step> {
+        for house in self.houses.iter() {
+            for value in 1..=LENGTH_DIMENSION {
+                let positions = self.positions(house, value);
+
+                if positions.len() == 1 {
+                    let index = positions[0];
+
+                    return Some(Self::step(
+                        Technique::HiddenSingle,
+                        &[index],
+                        Some((index, value)),
+                        vec![],
+                    ));
+                }
+            }
+        }
+
+        None
+    }
+
+    //////////
+    // Intersections
+
+    /// A value confined to one line or column of a region can't be elsewhere on that line or column
+    fn pointing_pair(&self) -> Option<Step> {
+        for region in self.houses.iter().skip(LENGTH * 2) {
+            for value in 1..=LENGTH_DIMENSION {
+                let positions = self.positions(region, value);
+
+                if positions.len() < 2 {
+                    continue;
+                }
+
+                for house in self.houses.iter().take(LENGTH * 2) {
+                    if !positions.iter().all(|index| house.contains(index)) {
+                        continue;
+                    }
+
+                    let targets = house
+                        .iter()
+                        .filter(|index| !region.contains(index))
+                        .map(|&index| (index, value));
+
+                    if let Some(step) =
+                        self.eliminations_step(Technique::PointingPair, &positions, targets)
+                    {
+                        return Some(step);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// A value confined to one region on a line or column can't be elsewhere in that region
+    fn box_line_reduction(&self) -> Option<Step> {
+        for house in self.houses.iter().take(LENGTH * 2) {
+            for value in 1..=LENGTH_DIMENSION {
+                let positions = self.positions(house, value);
+
+                if positions.len() < 2 {
+                    continue;
+                }
+
+                for region in self.houses.iter().skip(LENGTH * 2) {
+                    if !positions.iter().all(|index| region.contains(index)) {
+                        continue;
+                    }
+
+                    let targets = region
+                        .iter()
+                        .filter(|index| !house.contains(index))
+                        .map(|&index| (index, value));
+
+                    if let Some(step) =
+                        self.eliminations_step(Technique::BoxLineReduction, &positions, targets)
+                    {
+                        return Some(step);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    //////////
+    // Subsets
+
+    /// `size` boxes of a house sharing `size` candidates hold them, other boxes of the house can't
+    fn naked_subset(&self, technique: Technique, size: usize) -> Option<Step> {
+        for house in self.houses.iter() {
+            let unsolved: Vec<usize> = house
+                .iter()
+                .copied()
+                .filter(|&index| {
+                    let nb_candidates = self.candidates[index].count_ones() as usize;
+                    nb_candidates >= 2 && nb_candidates <= size
+                })
+                .collect();
+
+            for subset in combinations(&unsolved, size) {
+                let union = subset
+                    .iter()
+                    .fold(0, |mask, &index| mask | self.candidates[index]);
+
+                if union.count_ones() as usize != size {
+                    continue;
+                }
+
+                let targets = house
+                    .iter()
+                    .filter(|index| !subset.contains(index))
+                    .flat_map(|&index| {
+                        mask_to_values(union)
+                            .into_iter()
+                            .map(move |val| (index, val))
+                    });
+
+                if let Some(step) = self.eliminations_step(technique, &subset, targets) {
+                    return Some(step);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// `size` values only fitting in the same `size` boxes of a house, those boxes can't hold
+    /// anything else
+    fn hidden_subset(&self, technique: Technique, size: usize) -> Option<Step> {
+        for house in self.houses.iter() {
+            let values: Vec<usize> = (1..=LENGTH_DIMENSION)
+                .filter(|&value| {
+                    let nb_positions = self.positions(house, value).len();
+                    nb_positions >= 1 && nb_positions <= size
+                })
+                .map(|value| value as usize)
+                .collect();
+
+            for subset in combinations(&values, size) {
+                let mut cells: Vec<usize> = subset
+                    .iter()
+                    .flat_map(|&value| self.positions(house, value as u8))
+                    .collect();
+                cells.sort();
+                cells.dedup();
+
+                if cells.len() != size {
+                    continue;
+                }
+
+                let kept = subset
+                    .iter()
+                    .fold(0, |mask, &value| mask | value_to_mask(value as u8));
+
+                let targets = cells.iter().flat_map(|&index| {
+                    mask_to_values(self.candidates[index] & !kept)
+                        .into_iter()
+                        .map(move |val| (index, val))
+                });
+
+                if let Some(step) = self.eliminations_step(technique, &cells, targets) {
+                    return Some(step);
+                }
+            }
+        }
+
+        None
+    }
+
+    //////////
+    // Fishes
+
+    /// X-Wing, Swordfish and Jellyfish: a value confined to the same `size` columns on `size`
+    /// lines can't be elsewhere on those columns, and the other way around
+    fn fish(&self, technique: Technique, size: usize) -> Option<Step> {
+        let lines = &self.houses[0..LENGTH];
+        let columns = &self.houses[LENGTH..LENGTH * 2];
+
+        for (bases, covers) in [(lines, columns), (columns, lines)] {
+            for value in 1..=LENGTH_DIMENSION {
+                let candidate_bases: Vec<usize> = (0..LENGTH)
+                    .filter(|&base| {
+                        let nb_positions = self.positions(&bases[base], value).len();
+                        nb_positions >= 2 && nb_positions <= size
+                    })
+                    .collect();
+
+                for subset in combinations(&candidate_bases, size) {
+                    let cells: Vec<usize> = subset
+                        .iter()
+                        .flat_map(|&base| self.positions(&bases[base], value))
+                        .collect();
+
+                    let cover_indices: Vec<usize> = (0..LENGTH)
+                        .filter(|&cover| cells.iter().any(|index| covers[cover].contains(index)))
+                        .collect();
+
+                    if cover_indices.len() != size {
+                        continue;
+                    }
+
+                    let targets = cover_indices
+                        .iter()
+                        .flat_map(|&cover| covers[cover].iter().copied())
+                        .filter(|index| !cells.contains(index))
+                        .map(|index| (index, value));
+
+                    if let Some(step) = self.eliminations_step(technique, &cells, targets) {
+                        return Some(step);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    //////////
+    // Wings
+
+    /// A pivot {a, b} seeing pincers {a, c} and {b, c}: whichever the pivot holds, one pincer is c
+    fn xy_wing(&self) -> Option<Step> {
+        let bivalues: Vec<usize> = (0..NB_BOXES)
+            .filter(|&index| self.candidates[index].count_ones() == 2)
+            .collect();
+
+        for &pivot in bivalues.iter() {
+            let pivot_mask = self.candidates[pivot];
+
+            let pincers: Vec<usize> = bivalues
+                .iter()
+                .copied()
+                .filter(|&other| {
+                    let shared = self.candidates[other] & pivot_mask;
+                    sees(pivot, other) && shared.count_ones() == 1
+                })
+                .collect();
+
+            for (position, &first) in pincers.iter().enumerate() {
+                for &second in pincers.iter().skip(position + 1) {
+                    let first_mask = self.candidates[first];
+                    let second_mask = self.candidates[second];
+
+                    let z_mask = first_mask & second_mask & !pivot_mask;
+                    let covers_pivot = (first_mask | second_mask) & pivot_mask == pivot_mask;
+
+                    if z_mask.count_ones() != 1 || !covers_pivot {
+                        continue;
+                    }
+
+                    let z_value = z_mask.trailing_zeros() as u8 + 1;
+
+                    let targets = (0..NB_BOXES)
+                        .filter(|&index| {
+                            index != pivot && sees(index, first) && sees(index, second)
+                        })
+                        .map(|index| (index, z_value));
+
+                    if let Some(step) =
+                        self.eliminations_step(Technique::XYWing, &[pivot, first, second], targets)
+                    {
+                        return Some(step);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// A pivot {x, y, z} seeing pincers {x, z} and {y, z}: one of the three is z
+    fn xyz_wing(&self) -> Option<Step> {
+        for pivot in 0..NB_BOXES {
+            let pivot_mask = self.candidates[pivot];
+
+            if pivot_mask.count_ones() != 3 {
+                continue;
+            }
+
+            let pincers: Vec<usize> = (0..NB_BOXES)
+                .filter(|&other| {
+                    let mask = self.candidates[other];
+                    mask.count_ones() == 2 && mask & !pivot_mask == 0 && sees(pivot, other)
+                })
+                .collect();
+
+            for (position, &first) in pincers.iter().enumerate() {
+                for &second in pincers.iter().skip(position + 1) {
+                    let first_mask = self.candidates[first];
+                    let second_mask = self.candidates[second];
+
+                    let z_mask = first_mask & second_mask;
+
+                    if z_mask.count_ones() != 1 || first_mask | second_mask != pivot_mask {
+                        continue;
+                    }
+
+                    let z_value = z_mask.trailing_zeros() as u8 + 1;
+
+                    let targets = (0..NB_BOXES)
+                        .filter(|&index| {
+                            sees(index, pivot) && sees(index, first) && sees(index, second)
+                        })
+                        .map(|index| (index, z_value));
+
+                    if let Some(step) =
+                        self.eliminations_step(Technique::XYZWing, &[pivot, first, second], targets)
+                    {
+                        return Some(step);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    //////////
+    // Coloring
+
+    /// Chains the boxes being the only two places of a value in a house, alternating two colors
+    /// Two boxes of the same color seeing each other make that color false, and a box seeing both
+    /// colors can't hold the value
+    fn simple_coloring(&self) -> Option<Step> {
+        for value in 1..=LENGTH_DIMENSION {
+            let mut links: Vec<(usize, usize)> = vec![];
+
+            for house in self.houses.iter() {
+                let positions = self.positions(house, value);
+
+                if positions.len() == 2 {
+                    links.push((positions[0], positions[1]));
+                }
+            }
+
+            let mut colors: [Option<bool>; NB_BOXES] = [None; NB_BOXES];
+
+            for &(start, _) in links.iter() {
+                if colors[start].is_some() {
+                    continue;
+                }
+
+                let chain = color_chain(start, &links, &mut colors);
+
+                if chain.len() < 3 {
+                    continue;
+                }
+
+                // Color wrap
+                for color in [true, false] {
+                    let same_color: Vec<usize> = chain
+                        .iter()
+                        .copied()
+                        .filter(|&index| colors[index] == Some(color))
+                        .collect();
+
+                    let is_contradiction = same_color
+                        .iter()
+                        .enumerate()
+                        .any(|(pos, &a)| same_color.iter().skip(pos + 1).any(|&b| sees(a, b)));
+
+                    if is_contradiction {
+                        let targets = same_color.iter().map(|&index| (index, value));
+
+                        if let Some(step) =
+                            self.eliminations_step(Technique::SimpleColoring, &chain, targets)
+                        {
+                            return Some(step);
+                        }
+                    }
+                }
+
+                // Color trap
+                let targets = (0..NB_BOXES)
+                    .filter(|index| !chain.contains(index))
+                    .filter(|&index| {
+                        let sees_color = |color| {
+                            chain
+                                .iter()
+                                .any(|&other| colors[other] == Some(color) && sees(index, other))
+                        };
+
+                        sees_color(true) && sees_color(false)
+                    })
+                    .map(|index| (index, value));
+
+                if let Some(step) =
+                    self.eliminations_step(Technique::SimpleColoring, &chain, targets)
+                {
+                    return Some(step);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+////////////////////
+
+/// Two different boxes sharing a line, a column or a region
+fn sees(a: usize, b: usize) -> bool {
+    if a == b {
+        return false;
+    }
+
+    let (line_a, column_a, region_a) = index_to_coordinates(a);
+    let (line_b, column_b, region_b) = index_to_coordinates(b);
+
+    line_a == line_b || column_a == column_b || region_a == region_b
+}
+
+/// Colors every box linked to `start`, alternating colors, and returns them
+fn color_chain(
+    start: usize,
+    links: &[(usize, usize)],
+    colors: &mut [Option<bool>; NB_BOXES],
+) -> Vec<usize> {
+    let mut chain = vec![start];
+    let mut to_visit = vec![start];
+    colors[start] = Some(true);
+
+    while let Some(current) = to_visit.pop() {
+        let current_color = colors[current].unwrap_or(true);
+
+        for &(a, b) in links.iter() {
+            let other = match (a == current, b == current) {
+                (true, _) => b,
+                (_, true) => a,
+                _ => continue,
+            };
+
+            if colors[other].is_none() {
+                colors[other] = Some(!current_color);
+                chain.push(other);
+                to_visit.push(other);
+            }
+        }
+    }
+
+    chain
+}
+
+/// Every way of picking `size` elements in order of appearance
+fn combinations(items: &[usize], size: usize) -> Vec<Vec<usize>> {
+    if size == 0 {
+        return vec![vec![]];
+    }
+
+    let mut to_return = vec![];
+
+    for (position, item) in items.iter().enumerate() {
+        for mut rest in combinations(&items[position + 1..], size - 1) {
+            rest.insert(0, *item);
+            to_return.push(rest);
+        }
+    }
+
+    to_return
+}
@@ -1,7 +1,22 @@
+pub mod batch;
+pub mod board;
+pub mod canonical;
 pub mod cli;
 pub mod constants;
+pub mod constraint;
+pub mod date;
+pub mod dlx;
+pub mod edge_constraint;
+pub mod error;
 pub mod file;
 pub mod generator;
 pub mod grid;
+pub mod killer;
+pub mod layout;
+pub mod line_constraint;
+pub mod logic;
+pub mod multigrid;
+pub mod rating;
 pub mod solver;
+pub mod transform;
 pub mod validation;
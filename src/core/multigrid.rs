@@ -0,0 +1,547 @@
+use super::{
+    board::{all_candidates, pop_lowest, value_to_mask, Candidates},
+    constants::TO_BE_SOLVED,
+    error::SabitaError,
+    file::{is_line_file, read_with_sections, value_to_string, write_with_sections, Section},
+    generator::{generate_multi, remove_values_keeping_unique_multi},
+    grid::{Grid, GridValues},
+    layout::{InvalidLayout, Layout},
+    solver::NoSudokuSolutionFound,
+};
+
+use rand::Rng;
+
+////////////////////////////////////////
+
+/// Name of the file section giving the `line:column` of the top left box of each grid
+pub const GRIDS_SECTION: &str = "grids";
+
+/// Name of the file section giving the size of the grids, 9 when missing
+const GRID_SIZE_SECTION: &str = "grid-size";
+
+////////////////////////////////////////
+
+/// Several grids of the same layout laid on a common canvas, such as the five grids of a samurai
+/// sudoku, overlapping grids sharing whole regions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiLayout {
+    grid_layout: Layout,
+    /// (line, column) of the top left box of each grid on the canvas
+    offsets: Vec<(usize, usize)>,
+    height: usize,
+    width: usize,
+}
+
+////////////////////
+
+impl MultiLayout {
+    //////////
+    // Constructors
+
+    /// Grids must have rectangular regions and start on a region boundary, so that overlapping
+    /// grids share whole regions
+    pub fn new(grid_layout: Layout, offsets: Vec<(usize, usize)>) -> Result<Self, InvalidLayout> {
+        let Some((box_height, box_width)) = grid_layout.box_shape() else {
+            return Err(InvalidLayout::new(
+                "grids of jigsaw regions can not overlap".to_string(),
+            ));
+        };
+
+        if offsets.is_empty() {
+            return Err(InvalidLayout::new("no grid given".to_string()));
+        }
+
+        for (position, (line, column)) in offsets.iter().enumerate() {
+            if line % box_height != 0 || column % box_width != 0 {
+                return Err(InvalidLayout::new(format!(
+                    "grid {position} at [{line}:{column}] does not start on a region boundary"
+                )));
+            }
+
+            if offsets[..position].contains(&(*line, *column)) {
+                return Err(InvalidLayout::new(format!(
+                    "two grids start at [{line}:{column}]"
+                )));
+            }
+        }
+
+        let size = grid_layout.size();
+        let height = offsets.iter().map(|(line, _)| line + size).max().unwrap();
+        let width = offsets
+            .iter()
+            .map(|(_, column)| column + size)
+            .max()
+            .unwrap();
+
+        Ok(MultiLayout {
+            grid_layout,
+            offsets,
+            height,
+            width,
+        })
+    }
+
+    /// Classic samurai: four 9x9 grids at the corners of a 21x21 canvas, each sharing its inner
+    /// corner region with a fifth grid in the middle
+    pub fn samurai() -> Self {
+        let offsets = vec![(0, 0), (0, 12), (6, 6), (12, 0), (12, 12)];
+
+        MultiLayout::new(Layout::standard(), offsets).unwrap()
+    }
+
+    /// Grids of the usual layout for their size, placed by a `[grids]` section
+    pub fn from_section(section: &Section, size: usize) -> Result<Self, InvalidLayout> {
+        let Some(grid_layout) = Layout::for_size(size) else {
+            return Err(InvalidLayout::new(format!("no layout for grids of {size}")));
+        };
+
+        let offsets = section
+            .lines
+            .iter()
+            .map(|line| {
+                let (top, left) = line.split_once(':')?;
+
+                Some((top.trim().parse().ok()?, left.trim().parse().ok()?))
+            })
+            .collect::<Option<Vec<(usize, usize)>>>()
+            .ok_or_else(|| InvalidLayout::new("wrong grid position".to_string()))?;
+
+        MultiLayout::new(grid_layout, offsets)
+    }
+
+    //////////
+    // Accessors
+
+    pub fn grid_layout(&self) -> &Layout {
+        &self.grid_layout
+    }
+
+    pub fn offsets(&self) -> &[(usize, usize)] {
+        &self.offsets
+    }
+
+    pub fn nb_grids(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Number of lines of the canvas
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of columns of the canvas
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    //////////
+    // Methods
+
+    /// Grids covering a box of the canvas, along with the (line, column) of the box in each of them
+    pub fn grids_at(&self, line: usize, column: usize) -> Vec<(usize, usize, usize)> {
+        let size = self.grid_layout.size();
+
+        self.offsets
+            .iter()
+            .enumerate()
+            .filter(|(_, (top, left))| {
+                (*top..top + size).contains(&line) && (*left..left + size).contains(&column)
+            })
+            .map(|(grid, (top, left))| (grid, line - top, column - left))
+            .collect()
+    }
+
+    /// Checks a box of the canvas belongs to at least one grid
+    pub fn contains(&self, line: usize, column: usize) -> bool {
+        !self.grids_at(line, column).is_empty()
+    }
+
+    /// Values of one of the grids, cut out of the canvas
+    pub fn grid_values(&self, values: &GridValues, grid: usize) -> GridValues {
+        let (top, left) = self.offsets[grid];
+        let size = self.grid_layout.size();
+
+        values[top..top + size]
+            .iter()
+            .map(|line| line[left..left + size].to_vec())
+            .collect()
+    }
+
+    /// `[grids]` section of a file placing the grids
+    pub fn to_section(&self) -> Section {
+        Section {
+            name: GRIDS_SECTION.to_string(),
+            lines: self
+                .offsets
+                .iter()
+                .map(|(line, column)| format!("{line}:{column}"))
+                .collect(),
+        }
+    }
+}
+
+////////////////////////////////////////
+
+/// Flat representation of the canvas made for solving
+/// Every line, column and region of every grid is a unit keeping the mask of its values, a box
+/// shared by two grids updating the units of both, so a value placed in one grid prunes the
+/// candidates of the other
+#[derive(Debug, Clone)]
+pub struct MultiBoard {
+    width: usize,
+    all_candidates: Candidates,
+    boxes: Vec<u8>,
+    /// Units of each box, empty for the boxes out of every grid
+    units_of: Vec<Vec<usize>>,
+    units: Vec<Candidates>,
+}
+
+////////////////////
+
+impl MultiBoard {
+    //////////
+    // Constructors
+
+    pub fn new(values: &GridValues, layout: &MultiLayout) -> Self {
+        let size = layout.grid_layout.size();
+        let units_per_grid = 3 * size;
+
+        let mut units_of = vec![vec![]; layout.height * layout.width];
+
+        for line in 0..layout.height {
+            for column in 0..layout.width {
+                for (grid, grid_line, grid_column) in layout.grids_at(line, column) {
+                    let first_unit = grid * units_per_grid;
+                    let region = layout.grid_layout.region_of(grid_line, grid_column) as usize;
+
+                    units_of[line * layout.width + column].extend([
+                        first_unit + grid_line,
+                        first_unit + size + grid_column,
+                        first_unit + 2 * size + region,
+                    ]);
+                }
+            }
+        }
+
+        let mut board = MultiBoard {
+            width: layout.width,
+            all_candidates: all_candidates(size),
+            boxes: vec![TO_BE_SOLVED; layout.height * layout.width],
+            units_of,
+            units: vec![0; layout.nb_grids() * units_per_grid],
+        };
+
+        for (line, row) in values.iter().enumerate() {
+            for (column, value) in row.iter().enumerate() {
+                if *value != TO_BE_SOLVED {
+                    board.place(line * layout.width + column, *value);
+                }
+            }
+        }
+
+        board
+    }
+
+    //////////
+    // Accessors
+
+    pub fn get(&self, index: usize) -> u8 {
+        self.boxes[index]
+    }
+
+    /// Canvas of values, boxes out of every grid left to 0
+    pub fn get_values(&self) -> GridValues {
+        self.boxes
+            .chunks(self.width)
+            .map(|line| line.to_vec())
+            .collect()
+    }
+
+    /// Checks a box belongs to at least one grid
+    pub fn is_inside(&self, index: usize) -> bool {
+        !self.units_of[index].is_empty()
+    }
+
+    //////////
+    // Methods
+
+    /// Values that can go in the box without repeating one of the units of any grid holding it
+    pub fn candidates(&self, index: usize) -> Candidates {
+        let used = self.units_of[index]
+            .iter()
+            .fold(0, |used, unit| used | self.units[*unit]);
+
+        !used & self.all_candidates
+    }
+
+    pub fn place(&mut self, index: usize, value: u8) {
+        let mask = value_to_mask(value);
+
+        self.boxes[index] = value;
+
+        for unit in self.units_of[index].iter() {
+            self.units[*unit] |= mask;
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        let value = self.boxes[index];
+
+        if value == TO_BE_SOLVED {
+            return;
+        }
+
+        let mask = !value_to_mask(value);
+
+        self.boxes[index] = TO_BE_SOLVED;
+
+        for unit in self.units_of[index].iter() {
+            self.units[*unit] &= mask;
+        }
+    }
+
+    /// Missing box having the fewest candidates, along with them
+    /// Returns None when every grid is complete
+    pub fn most_constrained(&self) -> Option<(usize, Candidates)> {
+        let mut best: Option<(usize, Candidates)> = None;
+
+        for index in 0..self.boxes.len() {
+            if self.boxes[index] != TO_BE_SOLVED || !self.is_inside(index) {
+                continue;
+            }
+
+            let candidates = self.candidates(index);
+
+            let is_better = match best {
+                Some((_, best_candidates)) => {
+                    candidates.count_ones() < best_candidates.count_ones()
+                }
+                None => true,
+            };
+
+            if is_better {
+                best = Some((index, candidates));
+
+                if candidates.count_ones() <= 1 {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Counts the ways of completing every grid at once, stopping at `limit`
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut board = self.clone();
+        let mut nb_found = 0;
+
+        if limit > 0 {
+            board.search(limit, &mut nb_found);
+        }
+
+        nb_found
+    }
+
+    pub fn solve(&self) -> Result<GridValues, NoSudokuSolutionFound> {
+        let mut board = self.clone();
+
+        match board.search(1, &mut 0) {
+            true => Ok(board.get_values()),
+            false => Err(NoSudokuSolutionFound),
+        }
+    }
+
+    /// Depth first search filling the most constrained box first
+    /// Returns true once `limit` solutions have been found, the board then holding the last one
+    fn search(&mut self, limit: usize, nb_found: &mut usize) -> bool {
+        let Some((index, mut candidates)) = self.most_constrained() else {
+            *nb_found += 1;
+
+            return *nb_found >= limit;
+        };
+
+        while let Some(value) = pop_lowest(&mut candidates) {
+            self.place(index, value);
+
+            if self.search(limit, nb_found) {
+                return true;
+            }
+
+            self.remove(index);
+        }
+
+        false
+    }
+}
+
+////////////////////////////////////////
+
+/// Puzzle made of several overlapping grids, solved as a whole
+#[derive(Debug, Clone)]
+pub struct MultiGrid {
+    /// Every box of the canvas, 0 for the boxes out of every grid
+    pub values: GridValues,
+    pub layout: MultiLayout,
+}
+
+////////////////////
+
+impl MultiGrid {
+    //////////
+    // Constructors
+
+    /// Checks the canvas fits the layout and every grid is valid on its own
+    pub fn new(values: GridValues, layout: MultiLayout) -> Result<Self, SabitaError> {
+        if values.len() != layout.height {
+            return Err(SabitaError::Dimension(format!(
+                "Wrong number of lines: {}",
+                values.len()
+            )));
+        }
+
+        for (line, row) in values.iter().enumerate() {
+            if row.len() != layout.width {
+                return Err(SabitaError::Dimension(format!(
+                    "Line index {} has a different number of columns than {}",
+                    line, layout.width
+                )));
+            }
+
+            for (column, value) in row.iter().enumerate() {
+                if *value != TO_BE_SOLVED && !layout.contains(line, column) {
+                    return Err(SabitaError::Dimension(format!(
+                        "Value '{value}' at position {line};{column} is out of every grid"
+                    )));
+                }
+            }
+        }
+
+        let multi_grid = MultiGrid { values, layout };
+
+        // Grids check their own values
+        multi_grid.grids()?;
+
+        Ok(multi_grid)
+    }
+
+    /// Generates the grids, then removes `nb_to_remove` values while keeping a single solution
+    pub fn generate<R: Rng>(
+        layout: MultiLayout,
+        nb_to_remove: Option<u16>,
+        generator: &mut R,
+    ) -> Result<Self, SabitaError> {
+        let mut values = generate_multi(&layout, generator)?;
+
+        if let Some(to_remove) = nb_to_remove {
+            values = remove_values_keeping_unique_multi(&values, &layout, to_remove, generator)?;
+        }
+
+        MultiGrid::new(values, layout)
+    }
+
+    /// Reads a canvas followed by a `[grids]` section
+    pub fn from_file(path: String) -> Result<Self, SabitaError> {
+        let (values, sections) = read_with_sections(path)?;
+
+        let Some(section) = sections
+            .iter()
+            .find(|section| section.name == GRIDS_SECTION)
+        else {
+            return Err(SabitaError::Parse(format!(
+                "missing [{GRIDS_SECTION}] section"
+            )));
+        };
+
+        let size = match sections
+            .iter()
+            .find(|section| section.name == GRID_SIZE_SECTION)
+            .and_then(|section| section.lines.first())
+        {
+            Some(line) => line
+                .parse::<usize>()
+                .map_err(|err| SabitaError::Parse(format!("wrong grid size '{line}': {err}")))?,
+            None => Layout::standard().size(),
+        };
+
+        MultiGrid::new(values, MultiLayout::from_section(section, size)?)
+    }
+
+    //////////
+    // Accessors
+
+    /// Solving representation of the canvas
+    pub fn board(&self) -> MultiBoard {
+        MultiBoard::new(&self.values, &self.layout)
+    }
+
+    /// Each grid on its own, checking its values
+    pub fn grids(&self) -> Result<Vec<Grid>, SabitaError> {
+        (0..self.layout.nb_grids())
+            .map(|grid| {
+                Grid::with_layout(
+                    self.layout.grid_values(&self.values, grid),
+                    self.layout.grid_layout.clone(),
+                )
+            })
+            .collect()
+    }
+
+    //////////
+    // Methods
+
+    pub fn solve(&mut self) -> Result<(), SabitaError> {
+        self.values = self.board().solve()?;
+
+        Ok(())
+    }
+
+    /// Counts the solutions of the whole puzzle, up to `limit`
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        self.board().count_solutions(limit)
+    }
+
+    /// Prints the canvas, boxes out of every grid left blank
+    pub fn print(&self) {
+        for (line, row) in self.values.iter().enumerate() {
+            let text: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(column, value)| match self.layout.contains(line, column) {
+                    true => value_to_string(*value),
+                    false => " ".to_string(),
+                })
+                .collect();
+
+            println!("{}", text.join(" ").trim_end());
+        }
+    }
+
+    /// Writes the canvas and the `[grids]` section, along with the size of the grids unless 9
+    pub fn dump_file(&self, path: String) -> Result<(), SabitaError> {
+        let mut sections = vec![self.layout.to_section()];
+        let size = self.layout.grid_layout.size();
+
+        if size != Layout::standard().size() {
+            sections.push(Section {
+                name: GRID_SIZE_SECTION.to_string(),
+                lines: vec![size.to_string()],
+            });
+        }
+
+        write_with_sections(path, self.values.clone(), &sections)
+    }
+}
+
+////////////////////
+
+/// Checks a puzzle file describes overlapping grids rather than a single one
+pub fn is_multi_grid_file(path: String) -> Result<bool, SabitaError> {
+    if is_line_file(path.clone())? {
+        return Ok(false);
+    }
+
+    let (_, sections) = read_with_sections(path)?;
+
+    Ok(sections.iter().any(|section| section.name == GRIDS_SECTION))
+}
@@ -0,0 +1,123 @@
+use super::{
+    error::SabitaError,
+    grid::GridValues,
+    logic::{solve_logically, Technique},
+    solver::solve_with_stats,
+};
+
+use std::fmt;
+
+////////////////////////////////////////
+
+/// Score given to puzzles the logical solver can't finish, guessing being then required
+pub const GUESSING_DIFFICULTY: f32 = 7.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+    Diabolical,
+}
+
+impl Level {
+    pub fn from_score(score: f32) -> Self {
+        if score <= Technique::HiddenSingle.difficulty() {
+            Level::Easy
+        } else if score <= Technique::BoxLineReduction.difficulty() {
+            Level::Medium
+        } else if score <= Technique::HiddenTriple.difficulty() {
+            Level::Hard
+        } else if score < GUESSING_DIFFICULTY {
+            Level::Expert
+        } else {
+            Level::Diabolical
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "easy" => Some(Level::Easy),
+            "medium" => Some(Level::Medium),
+            "hard" => Some(Level::Hard),
+            "expert" => Some(Level::Expert),
+            "diabolical" => Some(Level::Diabolical),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Level::Easy => "easy",
+            Level::Medium => "medium",
+            Level::Hard => "hard",
+            Level::Expert => "expert",
+            Level::Diabolical => "diabolical",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+//////////
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rating {
+    pub score: f32,
+    pub level: Level,
+    /// None when the grid was already complete
+    pub hardest_technique: Option<Technique>,
+    pub nb_logical_steps: usize,
+    pub solved_logically: bool,
+    /// Dead ends the backtracking solver went through
+    pub nb_backtracks: usize,
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hardest = match &self.hardest_technique {
+            Some(technique) => technique.name(),
+            None => "[none]",
+        };
+
+        writeln!(f, "Score: {:.1}", self.score)?;
+        writeln!(f, "Level: {}", self.level)?;
+        writeln!(f, "Hardest technique: {hardest}")?;
+        writeln!(f, "Logical steps: {}", self.nb_logical_steps)?;
+        writeln!(f, "Solved logically: {}", self.solved_logically)?;
+        write!(f, "Backtracks: {}", self.nb_backtracks)
+    }
+}
+
+////////////////////////////////////////
+
+/// Rates the grid by the hardest technique a human needs to solve it
+/// Grids the techniques can't finish get past `GUESSING_DIFFICULTY`, more so the more the
+/// backtracking solver had to go back
+pub fn rate(values: &GridValues) -> Result<Rating, SabitaError> {
+    let logical = solve_logically(values)?;
+    let (_, stats) = solve_with_stats(values)?;
+
+    let hardest_technique = logical.hardest_technique();
+    let solved_logically = logical.is_solved();
+
+    let score = if solved_logically {
+        hardest_technique.map_or(0.0, |technique| technique.difficulty())
+    } else {
+        let guessing = (1.0 + stats.nb_backtracks as f32).log10();
+
+        GUESSING_DIFFICULTY + (guessing * 10.0).round() / 10.0
+    };
+
+    Ok(Rating {
+        score,
+        level: Level::from_score(score),
+        hardest_technique,
+        nb_logical_steps: logical.steps.len(),
+        solved_logically,
+        nb_backtracks: stats.nb_backtracks,
+    })
+}
@@ -1,7 +1,8 @@
 use super::{
-    constants::{LENGTH_DIMENSION, TO_BE_SOLVED},
-    grid::{location_to_region, BoxLocation, GridValues},
-    validation::validate_new_box,
+    board::{mask_to_values, pop_lowest, Board, Candidates},
+    dlx::DancingLinks,
+    error::SabitaError,
+    grid::{BoxLocation, GridValues},
 };
 
 use std::fmt;
@@ -23,8 +24,8 @@ impl fmt::Display for BoxSolutionNotFound {
 
 #[derive(Debug, Clone)]
 pub struct SortedSolution<'a> {
-    location: &'a BoxLocation,
-    solutions: Vec<u8>,
+    pub location: &'a BoxLocation,
+    pub solutions: Vec<u8>,
 }
 
 impl fmt::Display for SortedSolution<'_> {
@@ -35,14 +36,6 @@ impl fmt::Display for SortedSolution<'_> {
 
 //////////
 
-#[derive(Debug, Clone)]
-pub struct InvolvedSolutions<'a> {
-    current_box: &'a SortedSolution<'a>,
-    involved_forward: Vec<SortedSolution<'a>>,
-}
-
-//////////
-
 #[derive(Debug)]
 pub struct NoSudokuSolutionFound;
 
@@ -54,273 +47,308 @@ impl fmt::Display for NoSudokuSolutionFound {
 
 //////////
 
-/// Associate a box index with it's solution index
-type SolutionStore = Vec<(usize, usize)>;
+/// Counters gathered by the backtracking search
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SolveStats {
+    /// Values tried in a box
+    pub nb_placements: usize,
+    /// Values taken back because they led to a dead end
+    pub nb_backtracks: usize,
+}
+
+//////////
+
+/// Associate a box index with the candidates it still has to try
+type SolutionStore = Vec<(usize, Candidates)>;
 
 ////////////////////////////////////////
 
-pub fn solve(
-    grid_values: &GridValues,
-    missing_boxes: &[BoxLocation],
-) -> Result<GridValues, NoSudokuSolutionFound> {
-    let mut grid_copy = grid_values.clone();
-
-    let sols = get_solutions_complexity_sorted(grid_values, missing_boxes);
-    let with_involved = get_involved_solutions(&sols);
-
-    let mut involved_index = 0;
-    let mut store: SolutionStore = vec![];
-
-    while involved_index < with_involved.len() {
-        let box_sol = with_involved[involved_index].clone();
-        let InvolvedSolutions {
-            current_box:
-                SortedSolution {
-                    location: current_box_location,
-                    solutions: current_box_solutions,
-                },
-            mut involved_forward,
-        } = box_sol;
+/// Common interface of the solving engines, so callers can pick the one fitting their puzzles
+/// Engines work on boards, which know the layout of the grid
+pub trait Solver {
+    fn solve_board(&self, board: &Board) -> Result<GridValues, NoSudokuSolutionFound>;
 
-        let mut sol_found_index: Option<usize> = None;
+    /// Counts the solutions of the board, stopping once `limit` is reached
+    fn count_board_solutions(&self, board: &Board, limit: usize) -> usize;
 
-        let start = match search_store(&store, involved_index) {
-            Some(index) => store[index].1,
-            None => 0,
-        };
+    /// Solves a grid of the usual layout for its size
+    fn solve(&self, grid_values: &GridValues) -> Result<GridValues, SabitaError> {
+        Ok(self.solve_board(&Board::new(grid_values)?)?)
+    }
 
-        if start < current_box_solutions.len() {
-            for curr_sol_index in start..current_box_solutions.len() {
-                let curr_sol = &current_box_solutions[curr_sol_index];
-
-                let affected_sol_indices = appy_sol(
-                    &mut grid_copy,
-                    &mut involved_forward,
-                    current_box_location,
-                    curr_sol,
-                );
-
-                match validate_new_box(&grid_copy, current_box_location) {
-                    Ok(_) => {
-                        sol_found_index = Some(curr_sol_index);
-                        break;
-                    }
-                    Err(_) => {
-                        if current_box_solutions.len() == 1 {
-                            return Err(NoSudokuSolutionFound);
-                        }
-
-                        rollback_sol(
-                            &mut grid_copy,
-                            &mut involved_forward,
-                            current_box_location,
-                            affected_sol_indices,
-                            curr_sol,
-                        );
-                    }
-                }
-            }
-        } else {
-            grid_copy[current_box_location.line][current_box_location.column] = TO_BE_SOLVED;
-        }
+    /// Counts the solutions of a grid of the usual layout for its size, up to `limit`
+    fn count_solutions(
+        &self,
+        grid_values: &GridValues,
+        limit: usize,
+    ) -> Result<usize, SabitaError> {
+        Ok(self.count_board_solutions(&Board::new(grid_values)?, limit))
+    }
+}
 
-        match sol_found_index {
-            None => {
-                involved_index -= 1;
+/// Depth first search on the bitmask board, filling the most constrained box first
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Backtracking;
 
-                match search_store(&store, involved_index) {
-                    Some(index) => {
-                        let matched_val = store[index];
-                        store[index].1 += 1;
-                        store.retain(|&el| el.0 <= matched_val.0);
-                    }
-                    None => {
-                        store.push((involved_index, 1));
-                        store.retain(|&el| el.0 <= involved_index);
-                    }
-                };
-            }
-            Some(the_sol_index) => {
-                match search_store(&store, involved_index) {
-                    Some(index) => {
-                        store[index].1 = the_sol_index;
-                    }
-                    None => {
-                        store.push((involved_index, the_sol_index));
-                    }
-                };
+impl Solver for Backtracking {
+    fn solve_board(&self, board: &Board) -> Result<GridValues, NoSudokuSolutionFound> {
+        solve_board(board)
+    }
 
-                involved_index += 1;
-            }
+    fn count_board_solutions(&self, board: &Board, limit: usize) -> usize {
+        count_board_solutions(board, limit)
+    }
+}
+
+//////////
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Engine {
+    #[default]
+    Backtrack,
+    Dlx,
+}
+
+impl Engine {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "backtrack" => Some(Engine::Backtrack),
+            "dlx" => Some(Engine::Dlx),
+            _ => None,
         }
     }
 
-    Ok(grid_copy)
+    pub fn solver(&self) -> Box<dyn Solver> {
+        match self {
+            Engine::Backtrack => Box::new(Backtracking),
+            Engine::Dlx => Box::new(DancingLinks),
+        }
+    }
+}
+
+impl fmt::Display for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Engine::Backtrack => "backtrack",
+            Engine::Dlx => "dlx",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+////////////////////////////////////////
+
+pub fn solve(grid_values: &GridValues) -> Result<GridValues, SabitaError> {
+    Ok(solve_board(&Board::new(grid_values)?)?)
 }
 
-/// Returns index in store of matching box index
-fn search_store(store: &SolutionStore, box_index: usize) -> Option<usize> {
-    store.iter().position(|&r| r.0 == box_index)
+pub fn solve_board(board: &Board) -> Result<GridValues, NoSudokuSolutionFound> {
+    solve_board_with_stats(board).map(|(values, _)| values)
 }
 
-/// Updates the grid with a value at the given position and remove that value from the involved
-/// boxes possible solutions
-/// Returns affected involved_boxes index
-fn appy_sol(
-    grid_copy: &mut GridValues,
-    involved_boxes: &mut Vec<SortedSolution>,
-    loc: &BoxLocation,
-    value: &u8,
-) -> Vec<usize> {
-    grid_copy[loc.line][loc.column] = *value;
-
-    let mut affected_indices = vec![];
-
-    for (box_index, involved) in involved_boxes.iter_mut().enumerate() {
-        for (sol_index, sol) in involved.solutions.clone().into_iter().enumerate() {
-            if sol == *value {
-                involved.solutions.remove(sol_index);
-                affected_indices.push(box_index);
-            }
-        }
+/// Solves the grid and tells how much guessing it took
+pub fn solve_with_stats(grid_values: &GridValues) -> Result<(GridValues, SolveStats), SabitaError> {
+    Ok(solve_board_with_stats(&Board::new(grid_values)?)?)
+}
+
+fn solve_board_with_stats(
+    board: &Board,
+) -> Result<(GridValues, SolveStats), NoSudokuSolutionFound> {
+    let mut board = board.clone();
+    let mut nb_found = 0;
+    let mut stats = SolveStats::default();
+
+    if search(&mut board, 1, &mut nb_found, &mut stats) {
+        Ok((board.get_values(), stats))
+    } else {
+        Err(NoSudokuSolutionFound)
     }
+}
 
-    affected_indices
+/// Counts the ways of completing the grid, stopping the search once `limit` solutions are found
+/// A limit of 2 is enough to tell unsolvable, unique and multiple solutions apart
+pub fn count_solutions(values: &GridValues, limit: usize) -> Result<usize, SabitaError> {
+    Ok(count_board_solutions(&Board::new(values)?, limit))
 }
 
-/// Updates the grid with to be solved at the given location and put back the possibility in
-/// involved solutions
-fn rollback_sol(
-    grid_copy: &mut GridValues,
-    involved_boxes: &mut [SortedSolution],
-    loc: &BoxLocation,
-    affected_box_solutions: Vec<usize>,
-    value: &u8,
-) {
-    grid_copy[loc.line][loc.column] = TO_BE_SOLVED;
-
-    for index in affected_box_solutions {
-        involved_boxes[index].solutions.push(*value);
+pub fn count_board_solutions(board: &Board, limit: usize) -> usize {
+    let mut board = board.clone();
+    let mut nb_found = 0;
+
+    if limit > 0 {
+        search(&mut board, limit, &mut nb_found, &mut SolveStats::default());
     }
+
+    nb_found
 }
 
-/// For each box location, find which other boxes will be involved in a forward way, which means
-/// that if the vector passed says a location A comes before an other location B, A will mention B but
-/// not the other way arround
-pub fn get_involved_solutions<'a>(
-    box_solutions: &'a Vec<SortedSolution>,
-) -> Vec<InvolvedSolutions<'a>> {
-    let mut to_return = vec![];
-
-    for (index, sol) in box_solutions.iter().enumerate() {
-        let SortedSolution {
-            location:
-                BoxLocation {
-                    line,
-                    column,
-                    region,
-                },
-            ..
-        } = sol;
+/// Checks that the grid has one and only one way of being completed
+pub fn has_unique_solution(values: &GridValues) -> Result<bool, SabitaError> {
+    Ok(count_solutions(values, 2)? == 1)
+}
 
-        let mut involved_forward = vec![];
+pub fn board_has_unique_solution(board: &Board) -> bool {
+    count_board_solutions(board, 2) == 1
+}
 
-        if index + 1 < box_solutions.len() {
-            for other_box in box_solutions.iter().skip(index + 1) {
-                let other_box_location = other_box.location;
+/// Depth first search filling the most constrained box first
+/// Returns true once `limit` solutions have been found, the board then holding the last one
+fn search(board: &mut Board, limit: usize, nb_found: &mut usize, stats: &mut SolveStats) -> bool {
+    let Some((index, mut candidates)) = board.most_constrained() else {
+        *nb_found += 1;
 
-                let same_line = other_box_location.line == *line;
-                let same_col = other_box_location.column == *column;
-                let same_region = other_box_location.region == *region;
+        return *nb_found >= limit;
+    };
 
-                if same_line | same_col | same_region {
-                    involved_forward.push(other_box.clone());
-                }
-            }
-        }
+    while let Some(value) = pop_lowest(&mut candidates) {
+        board.place(index, value);
+        stats.nb_placements += 1;
 
-        let combo = InvolvedSolutions {
-            current_box: sol,
-            involved_forward,
-        };
+        if search(board, limit, nb_found, stats) {
+            return true;
+        }
 
-        to_return.push(combo);
+        board.remove(index);
+        stats.nb_backtracks += 1;
     }
 
-    to_return
+    false
 }
 
-/// Returns boxes, regions and solutions ordered by their number of possibilities (asc)
-pub fn get_solutions_complexity_sorted<'a>(
-    grid_values: &GridValues,
-    missing_boxes: &'a [BoxLocation],
-) -> Vec<SortedSolution<'a>> {
-    let mut solutions = vec![];
+////////////////////
 
-    for missing in missing_boxes.iter() {
-        let solution = get_box_solutions(grid_values, missing).unwrap();
-        solutions.push(solution);
+/// Lazily walks every completion of a grid, in a depth first way
+/// The store keeps, for each box filled so far, the candidates it has left to try so the search can
+/// resume right after the last solution it returned
+#[derive(Debug, Clone)]
+pub struct Solutions {
+    board: Board,
+    store: SolutionStore,
+    started: bool,
+    exhausted: bool,
+}
+
+impl Solutions {
+    pub fn new(grid_values: &GridValues) -> Result<Self, SabitaError> {
+        Ok(Solutions::from_board(Board::new(grid_values)?))
     }
 
-    let mut sorted_indices = (0..solutions.len()).collect::<Vec<usize>>();
-    sorted_indices.sort_by_key(|&i| solutions[i].len());
+    pub fn from_board(board: Board) -> Self {
+        Solutions {
+            board,
+            store: vec![],
+            started: false,
+            exhausted: false,
+        }
+    }
 
-    let mut locs_regions_solutions = vec![];
+    /// Replaces the value of the last filled box by its next candidate, going back up the store
+    /// while boxes have none left
+    fn backtrack(&mut self) {
+        while let Some((index, remaining)) = self.store.last_mut() {
+            self.board.remove(*index);
 
-    for index in sorted_indices {
-        let combo = SortedSolution {
-            location: &missing_boxes[index],
-            solutions: solutions[index].clone(),
-        };
+            if let Some(value) = pop_lowest(remaining) {
+                self.board.place(*index, value);
+                return;
+            }
 
-        locs_regions_solutions.push(combo);
+            self.store.pop();
+        }
+
+        self.exhausted = true;
     }
+}
 
-    locs_regions_solutions
+impl Iterator for Solutions {
+    type Item = GridValues;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.started {
+            self.backtrack();
+        }
+        self.started = true;
+
+        while !self.exhausted {
+            match self.board.most_constrained() {
+                None => return Some(self.board.get_values()),
+                Some((index, mut candidates)) => match pop_lowest(&mut candidates) {
+                    Some(value) => {
+                        self.board.place(index, value);
+                        self.store.push((index, candidates));
+                    }
+                    None => self.backtrack(),
+                },
+            }
+        }
+
+        None
+    }
 }
 
-pub fn get_box_solutions(
+////////////////////
+
+/// Returns boxes, regions and solutions ordered by their number of possibilities (asc)
+pub fn get_solutions_complexity_sorted<'a>(
     grid_values: &GridValues,
-    location: &BoxLocation,
-) -> Result<Vec<u8>, BoxSolutionNotFound> {
-    let mut answers: Vec<u8> = vec![];
+    missing_boxes: &'a [BoxLocation],
+) -> Result<Vec<SortedSolution<'a>>, SabitaError> {
+    let board = Board::new(grid_values)?;
+
+    let mut locs_regions_solutions: Vec<SortedSolution> = missing_boxes
+        .iter()
+        .map(|location| {
+            Ok(SortedSolution {
+                location,
+                solutions: mask_to_values(board.candidates(board_index(&board, location)?)),
+            })
+        })
+        .collect::<Result<_, SabitaError>>()?;
 
-    for possibility in (TO_BE_SOLVED + 1)..(LENGTH_DIMENSION + 1) {
-        let mut grid_to_test = grid_values.clone();
-        grid_to_test[location.line][location.column] = possibility;
+    locs_regions_solutions.sort_by_key(|sol| sol.solutions.len());
 
-        if validate_new_box(&grid_to_test, location).is_ok() {
-            answers.push(possibility);
-        }
-    }
+    Ok(locs_regions_solutions)
+}
+
+/// Values the box can still hold, failing when it has none or is out of the grid
+pub fn get_box_solutions(
+    grid_values: &GridValues,
+    location: &BoxLocation,
+) -> Result<Vec<u8>, SabitaError> {
+    let board = Board::new(grid_values)?;
+    let answers = mask_to_values(board.candidates(board_index(&board, location)?));
 
     if answers.is_empty() {
         Err(BoxSolutionNotFound {
             location: location.clone(),
-        })
+        }
+        .into())
     } else {
         Ok(answers)
     }
 }
 
-pub fn locate_missing_box(values: &GridValues) -> Vec<BoxLocation> {
-    let mut locations = vec![];
+pub fn locate_missing_box(values: &GridValues) -> Result<Vec<BoxLocation>, SabitaError> {
+    Ok(locate_missing_board_box(&Board::new(values)?))
+}
 
-    for (line, row) in values.iter().enumerate() {
-        for (column, value) in row.iter().enumerate() {
-            if *value == 0 {
-                let region = location_to_region(&line, &column).unwrap();
+pub fn locate_missing_board_box(board: &Board) -> Vec<BoxLocation> {
+    board
+        .missing_indices()
+        .into_iter()
+        .map(|index| board.location(index))
+        .collect()
+}
 
-                let loc = BoxLocation {
-                    line,
-                    column,
-                    region,
-                };
+fn board_index(board: &Board, location: &BoxLocation) -> Result<usize, SabitaError> {
+    let size = board.layout().size();
 
-                locations.push(loc);
-            }
-        }
+    if location.line >= size || location.column >= size {
+        return Err(SabitaError::Dimension(format!(
+            "Box {location} out of a grid of size {size}"
+        )));
     }
 
-    locations
+    Ok(location.line * size + location.column)
 }
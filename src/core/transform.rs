@@ -0,0 +1,427 @@
+use super::{
+    constants::{LENGTH_DIMENSION, TO_BE_SOLVED},
+    error::SabitaError,
+    grid::GridValues,
+};
+
+use rand::{seq::SliceRandom, Rng};
+
+////////////////////////////////////////
+
+const LENGTH: usize = LENGTH_DIMENSION as usize;
+
+/// Lines in a band, or columns in a stack
+const BAND_SIZE: usize = 3;
+
+/// Any combination of the moves keeping a sudoku valid: swapping lines within a band, bands,
+/// columns within a stack, stacks, transposing and relabeling the digits
+/// Rotations and reflections are combinations of those
+///
+/// The box [line][column] of the result holds the relabeled value found at
+/// [lines[line]][columns[column]] of the (transposed if needed) original grid
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Transformation {
+    transpose: bool,
+    lines: [usize; LENGTH],
+    columns: [usize; LENGTH],
+    /// New value of each value, TO_BE_SOLVED always staying in place
+    digits: [u8; LENGTH + 1],
+}
+
+////////////////////
+
+impl Transformation {
+    //////////
+    // Constructors
+
+    pub fn identity() -> Self {
+        let mut digits = [TO_BE_SOLVED; LENGTH + 1];
+        for (value, digit) in digits.iter_mut().enumerate() {
+            *digit = value as u8;
+        }
+
+        Transformation {
+            transpose: false,
+            lines: identity_map(),
+            columns: identity_map(),
+            digits,
+        }
+    }
+
+    /// Reorders the lines of a band, `order[i]` being the line of the band moved to position `i`
+    pub fn permute_lines_in_band(
+        band: usize,
+        order: [usize; BAND_SIZE],
+    ) -> Result<Self, SabitaError> {
+        check_band(band)?;
+        check_order(&order)?;
+
+        Ok(Transformation {
+            lines: permutation_within_band(band, order),
+            ..Transformation::identity()
+        })
+    }
+
+    /// Reorders the bands, `order[i]` being the band moved to position `i`
+    pub fn permute_bands(order: [usize; BAND_SIZE]) -> Result<Self, SabitaError> {
+        check_order(&order)?;
+
+        Ok(Transformation {
+            lines: band_permutation(order),
+            ..Transformation::identity()
+        })
+    }
+
+    /// Reorders the columns of a stack, `order[i]` being the column of the stack moved to
+    /// position `i`
+    pub fn permute_columns_in_stack(
+        stack: usize,
+        order: [usize; BAND_SIZE],
+    ) -> Result<Self, SabitaError> {
+        check_band(stack)?;
+        check_order(&order)?;
+
+        Ok(Transformation {
+            columns: permutation_within_band(stack, order),
+            ..Transformation::identity()
+        })
+    }
+
+    /// Reorders the stacks, `order[i]` being the stack moved to position `i`
+    pub fn permute_stacks(order: [usize; BAND_SIZE]) -> Result<Self, SabitaError> {
+        check_order(&order)?;
+
+        Ok(Transformation {
+            columns: band_permutation(order),
+            ..Transformation::identity()
+        })
+    }
+
+    pub fn swap_lines(line_a: usize, line_b: usize) -> Result<Self, SabitaError> {
+        if line_a >= LENGTH || line_b >= LENGTH {
+            return Err(SabitaError::Dimension(format!(
+                "Lines {line_a} and {line_b} are not both below {LENGTH}"
+            )));
+        }
+        if line_a / BAND_SIZE != line_b / BAND_SIZE {
+            return Err(SabitaError::Dimension(format!(
+                "Lines {line_a} and {line_b} are not in the same band"
+            )));
+        }
+
+        let mut lines = identity_map();
+        lines.swap(line_a, line_b);
+
+        Ok(Transformation {
+            lines,
+            ..Transformation::identity()
+        })
+    }
+
+    pub fn swap_columns(column_a: usize, column_b: usize) -> Result<Self, SabitaError> {
+        if column_a >= LENGTH || column_b >= LENGTH {
+            return Err(SabitaError::Dimension(format!(
+                "Columns {column_a} and {column_b} are not both below {LENGTH}"
+            )));
+        }
+        if column_a / BAND_SIZE != column_b / BAND_SIZE {
+            return Err(SabitaError::Dimension(format!(
+                "Columns {column_a} and {column_b} are not in the same stack"
+            )));
+        }
+
+        let mut columns = identity_map();
+        columns.swap(column_a, column_b);
+
+        Ok(Transformation {
+            columns,
+            ..Transformation::identity()
+        })
+    }
+
+    /// Mirrors the grid along its main diagonal
+    pub fn transpose() -> Self {
+        Transformation {
+            transpose: true,
+            ..Transformation::identity()
+        }
+    }
+
+    pub fn rotate_clockwise() -> Self {
+        Transformation {
+            transpose: true,
+            columns: reversed_map(),
+            ..Transformation::identity()
+        }
+    }
+
+    pub fn rotate_counter_clockwise() -> Self {
+        Transformation {
+            transpose: true,
+            lines: reversed_map(),
+            ..Transformation::identity()
+        }
+    }
+
+    pub fn rotate_half_turn() -> Self {
+        Transformation {
+            lines: reversed_map(),
+            columns: reversed_map(),
+            ..Transformation::identity()
+        }
+    }
+
+    /// Mirrors left and right
+    pub fn reflect_horizontally() -> Self {
+        Transformation {
+            columns: reversed_map(),
+            ..Transformation::identity()
+        }
+    }
+
+    /// Mirrors top and bottom
+    pub fn reflect_vertically() -> Self {
+        Transformation {
+            lines: reversed_map(),
+            ..Transformation::identity()
+        }
+    }
+
+    /// Mirrors the grid along its anti diagonal
+    pub fn reflect_anti_diagonally() -> Self {
+        Transformation {
+            transpose: true,
+            lines: reversed_map(),
+            columns: reversed_map(),
+            ..Transformation::identity()
+        }
+    }
+
+    /// Replaces each value `n` by `mapping[n - 1]`
+    pub fn relabel(mapping: [u8; LENGTH]) -> Result<Self, SabitaError> {
+        let mut seen = [false; LENGTH + 1];
+
+        for new_value in mapping.iter() {
+            if *new_value == TO_BE_SOLVED || *new_value > LENGTH_DIMENSION {
+                return Err(SabitaError::Dimension(format!(
+                    "Value '{new_value}' out of bound in the relabeling"
+                )));
+            }
+            if seen[*new_value as usize] {
+                return Err(SabitaError::Dimension(format!(
+                    "Value '{new_value}' used twice in the relabeling"
+                )));
+            }
+
+            seen[*new_value as usize] = true;
+        }
+
+        Ok(Transformation {
+            digits: relabeled_digits(mapping),
+            ..Transformation::identity()
+        })
+    }
+
+    /// Assembles already checked maps, `mapping[n - 1]` being the new value of `n`
+    pub(crate) fn from_parts(
+        transpose: bool,
+        lines: [usize; LENGTH],
+        columns: [usize; LENGTH],
+        mapping: [u8; LENGTH],
+    ) -> Self {
+        Transformation {
+            transpose,
+            lines,
+            columns,
+            digits: relabeled_digits(mapping),
+        }
+    }
+
+    /// Any element of the group, each one being as likely
+    pub fn random<R: Rng>(generator: &mut R) -> Self {
+        let mut bands = [0, 1, 2];
+        let mut stacks = [0, 1, 2];
+        bands.shuffle(generator);
+        stacks.shuffle(generator);
+
+        let mut to_return = Transformation {
+            transpose: generator.random_bool(0.5),
+            lines: band_permutation(bands),
+            columns: band_permutation(stacks),
+            ..Transformation::identity()
+        };
+
+        for band in 0..BAND_SIZE {
+            let mut order = [0, 1, 2];
+
+            order.shuffle(generator);
+            to_return = to_return.then(&Transformation {
+                lines: permutation_within_band(band, order),
+                ..Transformation::identity()
+            });
+
+            order.shuffle(generator);
+            to_return = to_return.then(&Transformation {
+                columns: permutation_within_band(band, order),
+                ..Transformation::identity()
+            });
+        }
+
+        let mut mapping = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        mapping.shuffle(generator);
+
+        to_return.then(&Transformation {
+            digits: relabeled_digits(mapping),
+            ..Transformation::identity()
+        })
+    }
+
+    //////////
+    // Methods
+
+    pub fn apply(&self, values: &GridValues) -> GridValues {
+        let mut to_return = vec![vec![TO_BE_SOLVED; LENGTH]; LENGTH];
+
+        for (line, row) in to_return.iter_mut().enumerate() {
+            for (column, value) in row.iter_mut().enumerate() {
+                let source = match self.transpose {
+                    false => values[self.lines[line]][self.columns[column]],
+                    true => values[self.columns[column]][self.lines[line]],
+                };
+
+                *value = self.digits[source as usize];
+            }
+        }
+
+        to_return
+    }
+
+    /// Transformation applying this one, then `other`
+    pub fn then(&self, other: &Transformation) -> Self {
+        let (lines, columns) = match other.transpose {
+            false => (
+                other.lines.map(|line| self.lines[line]),
+                other.columns.map(|column| self.columns[column]),
+            ),
+            true => (
+                other.lines.map(|line| self.columns[line]),
+                other.columns.map(|column| self.lines[column]),
+            ),
+        };
+
+        Transformation {
+            transpose: self.transpose != other.transpose,
+            lines,
+            columns,
+            digits: self.digits.map(|digit| other.digits[digit as usize]),
+        }
+    }
+
+    /// Transformation undoing this one
+    pub fn inverse(&self) -> Self {
+        let inverse_lines = invert_map(&self.lines);
+        let inverse_columns = invert_map(&self.columns);
+
+        let mut digits = [TO_BE_SOLVED; LENGTH + 1];
+        for (value, digit) in self.digits.iter().enumerate() {
+            digits[*digit as usize] = value as u8;
+        }
+
+        let (lines, columns) = match self.transpose {
+            false => (inverse_lines, inverse_columns),
+            true => (inverse_columns, inverse_lines),
+        };
+
+        Transformation {
+            transpose: self.transpose,
+            lines,
+            columns,
+            digits,
+        }
+    }
+}
+
+impl Default for Transformation {
+    fn default() -> Self {
+        Transformation::identity()
+    }
+}
+
+////////////////////
+
+fn identity_map() -> [usize; LENGTH] {
+    let mut map = [0; LENGTH];
+    for (index, element) in map.iter_mut().enumerate() {
+        *element = index;
+    }
+
+    map
+}
+
+fn reversed_map() -> [usize; LENGTH] {
+    identity_map().map(|index| LENGTH - 1 - index)
+}
+
+fn invert_map(map: &[usize; LENGTH]) -> [usize; LENGTH] {
+    let mut inverse = [0; LENGTH];
+    for (index, element) in map.iter().enumerate() {
+        inverse[*element] = index;
+    }
+
+    inverse
+}
+
+fn check_order(order: &[usize; BAND_SIZE]) -> Result<(), SabitaError> {
+    let mut sorted = *order;
+    sorted.sort();
+
+    match sorted == [0, 1, 2] {
+        true => Ok(()),
+        false => Err(SabitaError::Dimension(format!(
+            "Order {order:?} is not a permutation of 0, 1 and 2"
+        ))),
+    }
+}
+
+fn check_band(band: usize) -> Result<(), SabitaError> {
+    match band < BAND_SIZE {
+        true => Ok(()),
+        false => Err(SabitaError::Dimension(format!(
+            "Band '{band}' out of bound"
+        ))),
+    }
+}
+
+/// Digits of a mapping already checked to hold every value once, `mapping[n - 1]` being the new
+/// value of `n`
+fn relabeled_digits(mapping: [u8; LENGTH]) -> [u8; LENGTH + 1] {
+    let mut digits = [TO_BE_SOLVED; LENGTH + 1];
+
+    for (index, new_value) in mapping.iter().enumerate() {
+        digits[index + 1] = *new_value;
+    }
+
+    digits
+}
+
+/// Map of a band reordered by an order already checked
+fn permutation_within_band(band: usize, order: [usize; BAND_SIZE]) -> [usize; LENGTH] {
+    let mut map = identity_map();
+    for (position, source) in order.iter().enumerate() {
+        map[band * BAND_SIZE + position] = band * BAND_SIZE + source;
+    }
+
+    map
+}
+
+/// Map of the bands reordered by an order already checked
+fn band_permutation(order: [usize; BAND_SIZE]) -> [usize; LENGTH] {
+    let mut map = identity_map();
+    for (position, source) in order.iter().enumerate() {
+        for offset in 0..BAND_SIZE {
+            map[position * BAND_SIZE + offset] = source * BAND_SIZE + offset;
+        }
+    }
+
+    map
+}
@@ -1,38 +1,73 @@
 use super::{
-    constants::{LENGTH_DIMENSION, TO_BE_SOLVED},
-    grid::{region_to_location, BoxLocation, GridValues},
+    board::Board,
+    constants::TO_BE_SOLVED,
+    constraint::Constraint,
+    error::SabitaError,
+    grid::{check_dimensions, wrong_number_of_lines, BoxLocation, GridValues},
+    layout::Layout,
 };
 
-use std::fmt;
+use std::{fmt, sync::Arc};
 
 ////////////////////////////////////////
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ValidationErrorType {
     LINE,
     COLUMN,
     REGION,
+    /// Name of the broken constraint
+    CONSTRAINT(String),
 }
 
 #[derive(Debug)]
 pub struct ValidationError {
     err_type: ValidationErrorType,
+    /// Line, column or region holding the conflict, the flat index of the box for a constraint
     index: usize,
-    duplicated_pos: usize,
+    /// Value repeated or breaking the constraint
+    value: usize,
+    /// Boxes in conflict
+    cells: Vec<BoxLocation>,
+}
+
+impl ValidationError {
+    pub fn err_type(&self) -> &ValidationErrorType {
+        &self.err_type
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn value(&self) -> usize {
+        self.value
+    }
+
+    pub fn cells(&self) -> &[BoxLocation] {
+        &self.cells
+    }
 }
 
 impl fmt::Display for ValidationError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let zone = match self.err_type {
+        let zone = match &self.err_type {
             ValidationErrorType::LINE => "Line",
             ValidationErrorType::COLUMN => "Column",
             ValidationErrorType::REGION => "Region",
+            ValidationErrorType::CONSTRAINT(name) => {
+                return write!(
+                    f,
+                    "Constraint {} is not followed at box {}, value {}",
+                    name, self.index, self.value
+                );
+            }
         };
 
         write!(
             f,
             "{} index {} is not valid, duplicate value {}",
-            zone, self.index, self.duplicated_pos
+            zone, self.index, self.value
         )
     }
 }
@@ -40,10 +75,19 @@ impl fmt::Display for ValidationError {
 ////////////////////////////////////////
 
 /// Checks no duplication of values for line, columns and row
-pub fn validate(values: &GridValues) -> Result<(), ValidationError> {
-    for index in 0..(LENGTH_DIMENSION as usize) {
-        let result = validate_new_box(
+/// Fails with the first conflict, or when the grid has no usual layout for its number of lines
+pub fn validate(values: &GridValues) -> Result<(), SabitaError> {
+    validate_with_layout(values, &inferred_layout(values)?)
+}
+
+/// Checks no duplication of values for line, columns and the regions of the layout
+pub fn validate_with_layout(values: &GridValues, layout: &Layout) -> Result<(), SabitaError> {
+    check_dimensions(values, layout)?;
+
+    for index in 0..layout.size() {
+        let result = validate_box(
             values,
+            layout,
             &BoxLocation {
                 line: index,
                 column: index,
@@ -58,106 +102,309 @@ pub fn validate(values: &GridValues) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Checks the lines, columns and regions of the layout, then that every value follows the
+/// constraints
+pub fn validate_with_constraints(
+    values: &GridValues,
+    layout: &Layout,
+    constraints: &[Arc<dyn Constraint>],
+) -> Result<(), SabitaError> {
+    validate_with_layout(values, layout)?;
+
+    match constraint_conflicts(values, layout, constraints)
+        .into_iter()
+        .next()
+    {
+        Some(err) => Err(err.into()),
+        None => Ok(()),
+    }
+}
+
+/// Every conflict of the grid: one per value repeated in a line, a column or a region, listing
+/// the boxes holding it
+/// Fails when the grid has no usual layout for its number of lines or a value out of bound
+pub fn validate_all(values: &GridValues) -> Result<Vec<ValidationError>, SabitaError> {
+    validate_all_with_constraints(values, &inferred_layout(values)?, &[])
+}
+
+/// Every conflict of the grid under the layout, followed by one per box whose value breaks a
+/// constraint
+pub fn validate_all_with_constraints(
+    values: &GridValues,
+    layout: &Layout,
+    constraints: &[Arc<dyn Constraint>],
+) -> Result<Vec<ValidationError>, SabitaError> {
+    check_dimensions(values, layout)?;
+
+    let size = layout.size();
+    let mut errors = vec![];
+
+    for line in 0..size {
+        let boxes: Vec<(usize, usize)> = (0..size).map(|column| (line, column)).collect();
+        errors.extend(zone_conflicts(
+            values,
+            layout,
+            ValidationErrorType::LINE,
+            line,
+            &boxes,
+        ));
+    }
+
+    for column in 0..size {
+        let boxes: Vec<(usize, usize)> = (0..size).map(|line| (line, column)).collect();
+        errors.extend(zone_conflicts(
+            values,
+            layout,
+            ValidationErrorType::COLUMN,
+            column,
+            &boxes,
+        ));
+    }
+
+    for region in 0..size {
+        let boxes = layout.region_boxes(region as u8);
+        errors.extend(zone_conflicts(
+            values,
+            layout,
+            ValidationErrorType::REGION,
+            region,
+            &boxes,
+        ));
+    }
+
+    errors.extend(constraint_conflicts(values, layout, constraints));
+
+    Ok(errors)
+}
+
 pub fn validate_new_box(
     values: &GridValues,
     box_location: &BoxLocation,
-) -> Result<(), ValidationError> {
+) -> Result<(), SabitaError> {
+    let layout = inferred_layout(values)?;
+    check_dimensions(values, &layout)?;
+
+    validate_box(values, &layout, box_location)
+}
+
+fn validate_box(
+    values: &GridValues,
+    layout: &Layout,
+    box_location: &BoxLocation,
+) -> Result<(), SabitaError> {
     let BoxLocation {
         line,
         column,
         region,
     } = box_location;
 
-    let (is_line_valid, wrong_line_value) = is_line_valid(values, line);
+    let (is_line_valid, wrong_line_value) = is_line_valid(values, line)?;
 
     if !is_line_valid {
-        return Err(ValidationError {
+        let boxes: Vec<(usize, usize)> = (0..layout.size()).map(|column| (*line, column)).collect();
+
+        return Err(SabitaError::Conflict(ValidationError {
             err_type: ValidationErrorType::LINE,
             index: *line,
-            duplicated_pos: wrong_line_value.unwrap(),
-        });
+            value: wrong_line_value.unwrap(),
+            cells: boxes_holding(layout, values, &boxes, wrong_line_value.unwrap()),
+        }));
     }
 
-    let (is_column_valid, wrong_column_value) = is_column_valid(values, column);
+    let (is_column_valid, wrong_column_value) = is_column_valid(values, column)?;
 
     if !is_column_valid {
-        return Err(ValidationError {
+        let boxes: Vec<(usize, usize)> = (0..layout.size()).map(|line| (line, *column)).collect();
+
+        return Err(SabitaError::Conflict(ValidationError {
             err_type: ValidationErrorType::COLUMN,
             index: *column,
-            duplicated_pos: wrong_column_value.unwrap(),
-        });
+            value: wrong_column_value.unwrap(),
+            cells: boxes_holding(layout, values, &boxes, wrong_column_value.unwrap()),
+        }));
     }
 
-    let (is_region_valid, wrong_region_value) = is_region_valid(values, region);
+    let (is_region_valid, wrong_region_value) = is_layout_region_valid(values, layout, region)?;
 
     if !is_region_valid {
-        return Err(ValidationError {
+        let boxes = layout.region_boxes(*region);
+
+        return Err(SabitaError::Conflict(ValidationError {
             err_type: ValidationErrorType::REGION,
             index: *region as usize,
-            duplicated_pos: wrong_region_value.unwrap(),
-        });
+            value: wrong_region_value.unwrap(),
+            cells: boxes_holding(layout, values, &boxes, wrong_region_value.unwrap()),
+        }));
     }
 
     Ok(())
 }
 
-fn handle_index_out_of_bound(index: &usize) {
-    if *index > LENGTH_DIMENSION.into() {
-        panic!("Index '{index}' out of bound");
+/// One conflict per value found more than once among the boxes of a zone, in increasing order of
+/// values
+fn zone_conflicts(
+    values: &GridValues,
+    layout: &Layout,
+    err_type: ValidationErrorType,
+    index: usize,
+    boxes: &[(usize, usize)],
+) -> Vec<ValidationError> {
+    (1..=layout.size())
+        .filter_map(|value| {
+            let cells = boxes_holding(layout, values, boxes, value);
+
+            (cells.len() > 1).then(|| ValidationError {
+                err_type: err_type.clone(),
+                index,
+                value,
+                cells,
+            })
+        })
+        .collect()
+}
+
+/// One conflict per box whose value is not allowed by a constraint, the first one breaking it,
+/// listing the box along with the boxes it clashes with
+fn constraint_conflicts(
+    values: &GridValues,
+    layout: &Layout,
+    constraints: &[Arc<dyn Constraint>],
+) -> Vec<ValidationError> {
+    if constraints.is_empty() {
+        return vec![];
+    }
+
+    let board = Board::with_layout(values, layout);
+    let mut errors = vec![];
+
+    for index in 0..layout.nb_boxes() {
+        let value = board.get(index);
+
+        if value == TO_BE_SOLVED {
+            continue;
+        }
+
+        if let Some(constraint) = constraints
+            .iter()
+            .find(|constraint| !constraint.allows(&board, index, value))
+        {
+            let mut boxes = constraint.conflicting_boxes(&board, index, value);
+            boxes.push(index);
+            boxes.sort();
+
+            errors.push(ValidationError {
+                err_type: ValidationErrorType::CONSTRAINT(constraint.name().to_string()),
+                index,
+                value: value as usize,
+                cells: boxes.into_iter().map(|cell| board.location(cell)).collect(),
+            });
+        }
+    }
+
+    errors
+}
+
+/// Locations of the boxes holding the value
+fn boxes_holding(
+    layout: &Layout,
+    values: &GridValues,
+    boxes: &[(usize, usize)],
+    value: usize,
+) -> Vec<BoxLocation> {
+    boxes
+        .iter()
+        .copied()
+        .filter(|(line, column)| values[*line][*column] as usize == value)
+        .map(|(line, column)| BoxLocation {
+            line,
+            column,
+            region: layout.region_of(line, column),
+        })
+        .collect()
+}
+
+fn handle_index_out_of_bound(index: usize, bound: usize) -> Result<(), SabitaError> {
+    match index >= bound {
+        true => Err(SabitaError::Dimension(format!(
+            "Index '{index}' out of bound, below {bound} expected"
+        ))),
+        false => Ok(()),
     }
 }
 
-pub fn is_line_valid(values: &GridValues, line_index: &usize) -> (bool, Option<usize>) {
-    handle_index_out_of_bound(line_index);
+/// Usual layout for the size of the grid
+fn inferred_layout(values: &GridValues) -> Result<Layout, SabitaError> {
+    Layout::for_size(values.len()).ok_or_else(|| wrong_number_of_lines(values))
+}
+
+pub fn is_line_valid(
+    values: &GridValues,
+    line_index: &usize,
+) -> Result<(bool, Option<usize>), SabitaError> {
+    handle_index_out_of_bound(*line_index, values.len())?;
 
     let mut already_used = vec![];
 
     for value in values[*line_index].iter() {
         if already_used.contains(value) && *value != TO_BE_SOLVED {
-            return (false, Some(*value as usize));
+            return Ok((false, Some(*value as usize)));
         } else {
             already_used.push(*value);
         }
     }
 
-    (true, None)
+    Ok((true, None))
 }
 
-pub fn is_column_valid(values: &GridValues, column_index: &usize) -> (bool, Option<usize>) {
-    handle_index_out_of_bound(column_index);
+pub fn is_column_valid(
+    values: &GridValues,
+    column_index: &usize,
+) -> Result<(bool, Option<usize>), SabitaError> {
+    for line in values.iter() {
+        handle_index_out_of_bound(*column_index, line.len())?;
+    }
 
     let mut already_used = vec![];
 
-    for line in values.iter().take(LENGTH_DIMENSION.into()) {
+    for line in values.iter() {
         let value = line[*column_index];
 
         if already_used.contains(&value) && value != TO_BE_SOLVED {
-            return (false, Some(value as usize));
+            return Ok((false, Some(value as usize)));
         } else {
             already_used.push(value);
         }
     }
 
-    (true, None)
+    Ok((true, None))
 }
 
-pub fn is_region_valid(values: &GridValues, region_index: &u8) -> (bool, Option<usize>) {
-    handle_index_out_of_bound(&(*region_index as usize));
+pub fn is_region_valid(
+    values: &GridValues,
+    region_index: &u8,
+) -> Result<(bool, Option<usize>), SabitaError> {
+    is_layout_region_valid(values, &inferred_layout(values)?, region_index)
+}
 
-    let (start_row, start_column) = region_to_location(region_index);
-    let third_of_length = (LENGTH_DIMENSION / 3) as usize;
+pub fn is_layout_region_valid(
+    values: &GridValues,
+    layout: &Layout,
+    region_index: &u8,
+) -> Result<(bool, Option<usize>), SabitaError> {
+    check_dimensions(values, layout)?;
+    handle_index_out_of_bound(*region_index as usize, layout.size())?;
 
     let mut already_used = vec![];
 
-    for row in values.iter().skip(start_row).take(third_of_length) {
-        for value in row.iter().skip(start_column).take(third_of_length) {
-            if already_used.contains(&value) && *value != TO_BE_SOLVED {
-                return (false, Some(*value as usize));
-            } else {
-                already_used.push(value);
-            }
+    for (line, column) in layout.region_boxes(*region_index) {
+        let value = values[line][column];
+
+        if already_used.contains(&value) && value != TO_BE_SOLVED {
+            return Ok((false, Some(value as usize)));
+        } else {
+            already_used.push(value);
         }
     }
 
-    (true, None)
+    Ok((true, None))
 }
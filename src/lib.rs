@@ -1,3 +1,5 @@
 pub mod assets;
 pub mod core;
 pub mod utils;
+
+pub use core::error::SabitaError;
@@ -1,30 +1,172 @@
-use sabita::core::{
-    cli::{parse_args, ArgParsed, ACTION},
-    constants::{PKG_NAME, PKG_VERSION},
-    grid::Grid,
+use sabita::{
+    core::{
+        batch::solve_collection,
+        cli::{parse_args, ArgParsed, Variant, ACTION},
+        constants::{PKG_NAME, PKG_VERSION},
+        date::Date,
+        file::{is_line_file, Format},
+        generator::{daily, generate_many, random_seed, seeded_rng},
+        grid::Grid,
+        multigrid::{is_multi_grid_file, MultiGrid, MultiLayout},
+        rating::Level,
+    },
+    SabitaError,
 };
 
-use std::process::exit;
+use std::{process::exit, thread};
 
 ////////////////////////////////////////
 
 fn main() {
+    if let Err(err) = parse_args().and_then(run) {
+        eprintln!("{err}");
+
+        exit(1);
+    }
+}
+
+fn run(args: ArgParsed) -> Result<(), SabitaError> {
     let ArgParsed {
         action,
         path,
+        output,
         nb_missing,
-    } = parse_args();
+        engine,
+        difficulty,
+        seed,
+        date,
+        layout,
+        variant,
+        format,
+        count,
+        threads,
+    } = args;
 
     match action {
+        ACTION::Solve if is_multi_grid_file(path.clone().unwrap())? => {
+            let mut multi_grid = MultiGrid::from_file(path.unwrap())?;
+            multi_grid.solve()?;
+            multi_grid.print();
+        }
         ACTION::Solve => {
-            let mut grid = Grid::from_file(path.unwrap());
-            grid.solve();
-            grid.print();
+            let grids = Grid::collection_from_file(path.unwrap())?;
+
+            for (position, mut grid) in grids.into_iter().enumerate() {
+                grid.solve_with(engine.solver().as_ref())?;
+
+                match format {
+                    Format::Grid if position > 0 => {
+                        println!();
+                        grid.print();
+                    }
+                    Format::Grid => grid.print(),
+                    Format::Line => println!("{}", grid.to_line()),
+                }
+            }
+        }
+        ACTION::BatchSolve => {
+            println!("{}", solve_collection(path.unwrap(), output.unwrap())?);
         }
         ACTION::Generate => {
-            let grid = Grid::generate(nb_missing);
-            grid.print();
-            grid.dump_file(path.unwrap());
+            let seed = seed.unwrap_or_else(random_seed);
+            println!("Seed: {seed}");
+
+            let mut generator = seeded_rng(seed);
+
+            if let Some(count) = count {
+                let nb_threads = threads.unwrap_or_else(|| {
+                    thread::available_parallelism().map_or(1, |nb_threads| nb_threads.get())
+                });
+                let path = path.unwrap();
+
+                let report = generate_many(
+                    count,
+                    nb_threads,
+                    nb_missing,
+                    difficulty,
+                    seed,
+                    path.clone(),
+                )?;
+
+                println!(
+                    "{count} puzzles written to {path}, {} duplicates and {} failed attempts left out",
+                    report.nb_duplicates, report.nb_failed
+                );
+            } else if variant == Some(Variant::Samurai) {
+                let multi_grid =
+                    MultiGrid::generate(MultiLayout::samurai(), nb_missing, &mut generator)?;
+                multi_grid.print();
+                multi_grid.dump_file(path.unwrap())?;
+            } else {
+                let grid = match (variant, layout) {
+                    (Some(Variant::Killer), layout) => {
+                        Grid::generate_killer(layout.unwrap_or_default(), &mut generator)
+                    }
+                    (_, Some(layout)) => {
+                        Grid::generate_with_layout(layout, nb_missing, &mut generator)
+                    }
+                    (_, None) => Grid::generate(nb_missing, difficulty, &mut generator),
+                }?;
+
+                match format {
+                    Format::Grid => grid.print(),
+                    Format::Line => println!("{}", grid.to_line()),
+                }
+                grid.dump_file_as(path.unwrap(), format)?;
+            }
+        }
+        ACTION::Check => {
+            let nb_solutions = match is_multi_grid_file(path.clone().unwrap())? {
+                true => MultiGrid::from_file(path.unwrap())?.count_solutions(2),
+                false => Grid::from_file(path.unwrap())?.count_solutions(2),
+            };
+
+            match nb_solutions {
+                0 => println!("Unsolvable: no solution found"),
+                1 => println!("Unique: exactly one solution"),
+                _ => println!("Multiple: more than one solution"),
+            }
+        }
+        ACTION::Validate => {
+            let conflicts = Grid::conflicts_in_file(path.unwrap())?;
+
+            if conflicts.is_empty() {
+                println!("Valid: no conflict found");
+            }
+
+            for conflict in conflicts {
+                let cells: Vec<String> = conflict
+                    .cells()
+                    .iter()
+                    .map(|cell| cell.to_string())
+                    .collect();
+
+                println!("{conflict}: {}", cells.join(" "));
+            }
+        }
+        ACTION::Rate => {
+            let grid = Grid::from_file(path.unwrap())?;
+
+            println!("{}", grid.rate()?);
+        }
+        ACTION::Canon if is_line_file(path.clone().unwrap())? => {
+            for grid in Grid::collection_from_file(path.unwrap())? {
+                println!("{} {}", grid.fingerprint()?, grid.canonical()?.to_line());
+            }
+        }
+        ACTION::Canon => {
+            let grid = Grid::from_file(path.unwrap())?;
+
+            grid.canonical()?.print();
+            println!("{}", grid.fingerprint()?);
+        }
+        ACTION::Daily => {
+            let date = date.unwrap_or_else(Date::today);
+            let level = difficulty.unwrap_or(Level::Medium);
+
+            println!("Daily {level} sudoku of {date}");
+
+            Grid::new(daily(&date, level)?)?.print();
         }
         ACTION::Version => {
             version();
@@ -36,6 +178,16 @@ fn main() {
             println!();
             help_solver();
             println!();
+            help_check();
+            println!();
+            help_validate();
+            println!();
+            help_rate();
+            println!();
+            help_canon();
+            println!();
+            help_daily();
+            println!();
         }
         ACTION::HelpGenerate => {
             eprintln!("Wrong args for command generate\n");
@@ -47,9 +199,41 @@ fn main() {
             eprintln!("Wrong args for command solve\n");
             help_solver();
 
+            exit(1);
+        }
+        ACTION::HelpCheck => {
+            eprintln!("Wrong args for command check\n");
+            help_check();
+
+            exit(1);
+        }
+        ACTION::HelpValidate => {
+            eprintln!("Wrong args for command validate\n");
+            help_validate();
+
+            exit(1);
+        }
+        ACTION::HelpRate => {
+            eprintln!("Wrong args for command rate\n");
+            help_rate();
+
+            exit(1);
+        }
+        ACTION::HelpDaily => {
+            eprintln!("Wrong args for command daily\n");
+            help_daily();
+
+            exit(1);
+        }
+        ACTION::HelpCanon => {
+            eprintln!("Wrong args for command canon\n");
+            help_canon();
+
             exit(1);
         }
     }
+
+    Ok(())
 }
 
 ////////////////////
@@ -60,15 +244,73 @@ fn version() {
 
 fn help_solver() {
     println!("Solver:");
-    println!("         {PKG_NAME} s <file/to/solve>");
+    println!("         {PKG_NAME} s <file/to/solve> [--engine backtrack|dlx] [--format grid|line]");
+    println!("         {PKG_NAME} s --batch <collection/to/solve> <file/to/create>");
     println!("Example:");
     println!("         {PKG_NAME} s sudoku.example");
+    println!("         {PKG_NAME} s sudoku.example --engine dlx");
+    println!("         {PKG_NAME} s collection.txt --format line");
+    println!("         {PKG_NAME} s --batch collection.txt solutions.txt");
 }
 
 fn help_generate() {
     println!("Generator:");
     println!("           {PKG_NAME} g <file/to/create> [optional number of missing boxes]");
+    println!("           {PKG_NAME} g <file/to/create> --difficulty easy|medium|hard|expert");
+    println!("           {PKG_NAME} g <file/to/create> [...] --size 4|6|9|12|16|25");
+    println!("           {PKG_NAME} g <file/to/create> [...] --seed <number>");
+    println!("           {PKG_NAME} g <file/to/create> [...] --format grid|line");
+    println!("           {PKG_NAME} g <file/to/create> <number>|--difficulty ... --count <number>");
+    println!("           {PKG_NAME} g <file/to/create> [...] --count <number> --threads <number>");
+    println!("           {PKG_NAME} g <file/to/create> --variant killer [--size ...]");
+    println!("           {PKG_NAME} g <file/to/create> [...] --variant samurai");
     println!("Example:");
     println!("           {PKG_NAME} g sudoku.txt");
     println!("           {PKG_NAME} g sudoku.txt 52");
+    println!("           {PKG_NAME} g sudoku.txt --difficulty hard");
+    println!("           {PKG_NAME} g sudoku.txt 52 --seed 42");
+    println!("           {PKG_NAME} g sudoku.txt 52 --format line");
+    println!("           {PKG_NAME} g book.txt 50 --count 10000 --threads 8");
+    println!("           {PKG_NAME} g hexadoku.txt 120 --size 16");
+    println!("           {PKG_NAME} g killer.txt --variant killer");
+    println!("           {PKG_NAME} g samurai.txt 240 --variant samurai");
+}
+
+fn help_check() {
+    println!("Checker:");
+    println!("         {PKG_NAME} check <file/to/check>");
+    println!("Example:");
+    println!("         {PKG_NAME} check sudoku.example");
+}
+
+fn help_validate() {
+    println!("Validator:");
+    println!("           {PKG_NAME} validate <file/to/validate>");
+    println!("Example:");
+    println!("           {PKG_NAME} validate sudoku.example");
+}
+
+fn help_rate() {
+    println!("Rater:");
+    println!("       {PKG_NAME} rate <file/to/rate>");
+    println!("Example:");
+    println!("       {PKG_NAME} rate sudoku.example");
+}
+
+fn help_canon() {
+    println!("Canonical form:");
+    println!("                {PKG_NAME} canon <file/to/canonicalize>");
+    println!("Example:");
+    println!("                {PKG_NAME} canon sudoku.example");
+    println!("                {PKG_NAME} canon collection.example");
+}
+
+fn help_daily() {
+    println!("Daily puzzle:");
+    println!(
+        "              {PKG_NAME} daily [--date YYYY-MM-DD] [--difficulty easy|medium|hard|expert]"
+    );
+    println!("Example:");
+    println!("              {PKG_NAME} daily");
+    println!("              {PKG_NAME} daily --date 2024-12-25 --difficulty hard");
 }
@@ -0,0 +1,29 @@
+use std::{env, fs};
+
+////////////////////////////////////////
+
+/// File of the temporary directory, removed when dropped even if the test fails before
+pub struct TempFile {
+    path: String,
+}
+
+impl TempFile {
+    /// Unique to the test run, `name` telling apart the files of a run
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("sabita_{name}_{}.txt", std::process::id()));
+
+        TempFile {
+            path: path.to_str().unwrap().to_string(),
+        }
+    }
+
+    pub fn path(&self) -> String {
+        self.path.clone()
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod constraint {
+    use sabita::core::board::{mask_to_values, Board};
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::constraint::{
+        from_section, AntiKing, AntiKnight, Constraint, Diagonal, ExtraRegions, NonConsecutive,
+        Parity, ParityCells,
+    };
+    use sabita::core::file::Section;
+    use sabita::core::layout::Layout;
+    use sabita::core::validation::validate_with_constraints;
+
+    use std::sync::Arc;
+
+    ////////////////////
+
+    /// Empty 9x9 board holding a single value
+    fn board_with(line: usize, column: usize, value: u8) -> Board {
+        let mut values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        values[line][column] = value;
+
+        Board::new(&values).unwrap()
+    }
+
+    fn allowed(constraint: &dyn Constraint, board: &Board, index: usize) -> Vec<u8> {
+        mask_to_values(constraint.prune(board, index, board.all_candidates()))
+    }
+
+    #[test]
+    fn diagonal() {
+        let board = board_with(0, 0, 5);
+
+        assert!(!Diagonal.allows(&board, 80, 5));
+        assert!(!Diagonal.allows(&board, 40, 5));
+        assert!(Diagonal.allows(&board, 8, 5));
+        assert!(Diagonal.allows(&board, 0, 5));
+    }
+
+    #[test]
+    fn anti_knight() {
+        let board = board_with(4, 4, 7);
+
+        assert!(!AntiKnight.allows(&board, 2 * 9 + 3, 7));
+        assert!(!AntiKnight.allows(&board, 5 * 9 + 6, 7));
+        assert!(AntiKnight.allows(&board, 5 * 9 + 5, 7));
+    }
+
+    #[test]
+    fn anti_king() {
+        let board = board_with(4, 4, 7);
+
+        assert!(!AntiKing.allows(&board, 3 * 9 + 3, 7));
+        assert!(!AntiKing.allows(&board, 5 * 9 + 4, 7));
+        assert!(AntiKing.allows(&board, 6 * 9 + 4, 7));
+    }
+
+    #[test]
+    fn non_consecutive() {
+        let board = board_with(4, 4, 5);
+
+        assert_eq!(
+            allowed(&NonConsecutive, &board, 4 * 9 + 5),
+            vec![1, 2, 3, 5, 7, 8, 9]
+        );
+        assert_eq!(allowed(&NonConsecutive, &board, 5 * 9 + 5).len(), 9);
+    }
+
+    #[test]
+    fn windoku() {
+        let board = board_with(1, 1, 3);
+        let windoku = ExtraRegions::windoku();
+
+        assert!(!windoku.allows(&board, 3 * 9 + 3, 3));
+        assert!(windoku.allows(&board, 4 * 9 + 4, 3));
+        assert!(windoku.allows(&board, 5 * 9 + 5, 3));
+    }
+
+    #[test]
+    fn parity() {
+        let board = Board::empty(&Layout::standard());
+        let cells = ParityCells::new(9, vec![(0, Parity::Even), (1, Parity::Odd)]);
+
+        assert_eq!(allowed(&cells, &board, 0), vec![2, 4, 6, 8]);
+        assert_eq!(allowed(&cells, &board, 1), vec![1, 3, 5, 7, 9]);
+        assert_eq!(allowed(&cells, &board, 2).len(), 9);
+    }
+
+    #[test]
+    fn board_candidates_follow_constraints() {
+        let constraints: Vec<Arc<dyn Constraint>> = vec![Arc::new(Diagonal)];
+        let board = board_with(0, 0, 5).with_constraints(&constraints);
+
+        assert!(!board.can_place(80, 5));
+        assert!(board_with(0, 0, 5).can_place(80, 5));
+    }
+
+    #[test]
+    fn validation() {
+        let mut values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        values[0][0] = 5;
+        values[8][8] = 5;
+
+        let layout = Layout::standard();
+        let constraints: Vec<Arc<dyn Constraint>> = vec![Arc::new(Diagonal)];
+
+        assert!(validate_with_constraints(&values, &layout, &[]).is_ok());
+
+        let err = validate_with_constraints(&values, &layout, &constraints).unwrap_err();
+        assert!(err.to_string().contains("diagonal"));
+    }
+
+    #[test]
+    fn sections() {
+        let layout = Layout::standard();
+
+        let section = |name: &str, lines: Vec<&str>| Section {
+            name: name.to_string(),
+            lines: lines.iter().map(|line| line.to_string()).collect(),
+        };
+
+        let diagonal = from_section(&section("diagonal", vec![]), &layout);
+        assert_eq!(diagonal.unwrap().unwrap().name(), "diagonal");
+
+        let windoku = ExtraRegions::windoku().to_section();
+        let read_back = from_section(&windoku, &layout).unwrap().unwrap();
+        assert_eq!(read_back.to_section().lines, windoku.lines);
+
+        let parity = section("parity", vec!["eo.......", "........."]);
+        assert!(from_section(&parity, &layout).unwrap().is_err());
+
+        assert!(from_section(&section("unknown", vec![]), &layout).is_none());
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod edges {
+    use sabita::core::board::{mask_to_values, Board};
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::constraint::{from_section, Constraint};
+    use sabita::core::edge_constraint::{EdgeConstraint, EdgeFamily, EdgeMark};
+    use sabita::core::file::Section;
+    use sabita::core::generator::{generate, remove_values_keeping_unique_with_constraints};
+    use sabita::core::grid::Grid;
+    use sabita::core::layout::Layout;
+    use sabita::core::validation::validate_with_constraints;
+    use sabita::SabitaError;
+
+    use std::sync::Arc;
+
+    ////////////////////
+
+    fn allowed(edges: &EdgeConstraint, neighbour_value: u8) -> Vec<u8> {
+        let mut values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        values[0][1] = neighbour_value;
+
+        let board = Board::new(&values).unwrap();
+
+        mask_to_values(edges.prune(&board, 0, board.all_candidates()))
+    }
+
+    fn single_mark(family: EdgeFamily, mark: EdgeMark) -> EdgeConstraint {
+        EdgeConstraint::new(family, &Layout::standard(), &[(0, 1, mark)], false).unwrap()
+    }
+
+    #[test]
+    fn marks() {
+        assert!(EdgeMark::White.holds(4, 5));
+        assert!(EdgeMark::Black.holds(8, 4));
+        assert!(EdgeMark::X.holds(3, 7));
+        assert!(EdgeMark::V.holds(1, 4));
+        assert!(!EdgeMark::Black.holds(3, 5));
+    }
+
+    #[test]
+    fn marked_edges() {
+        let white = single_mark(EdgeFamily::Kropki, EdgeMark::White);
+        let black = single_mark(EdgeFamily::Kropki, EdgeMark::Black);
+        let x = single_mark(EdgeFamily::XV, EdgeMark::X);
+        let v = single_mark(EdgeFamily::XV, EdgeMark::V);
+
+        assert_eq!(allowed(&white, 5), vec![4, 6]);
+        assert_eq!(allowed(&black, 4), vec![2, 8]);
+        assert_eq!(allowed(&black, TO_BE_SOLVED), vec![1, 2, 3, 4, 6, 8]);
+        assert_eq!(allowed(&x, TO_BE_SOLVED), vec![1, 2, 3, 4, 6, 7, 8, 9]);
+        assert_eq!(allowed(&v, TO_BE_SOLVED), vec![1, 2, 3, 4]);
+        assert_eq!(allowed(&v, 1), vec![4]);
+    }
+
+    #[test]
+    fn negative_constraint() {
+        let layout = Layout::standard();
+        let kropki = EdgeConstraint::new(EdgeFamily::Kropki, &layout, &[], true).unwrap();
+        let xv = EdgeConstraint::new(EdgeFamily::XV, &layout, &[], true).unwrap();
+
+        assert_eq!(allowed(&kropki, 4), vec![1, 4, 6, 7, 9]);
+        assert_eq!(allowed(&xv, 3), vec![1, 3, 4, 5, 6, 8, 9]);
+        assert_eq!(allowed(&kropki, TO_BE_SOLVED).len(), 9);
+    }
+
+    #[test]
+    fn invalid_marks() {
+        let layout = Layout::standard();
+
+        for (cell, other, mark) in [
+            (0, 2, EdgeMark::White),
+            (8, 9, EdgeMark::White),
+            (80, 81, EdgeMark::White),
+            (0, 1, EdgeMark::X),
+        ] {
+            let marks = [(cell, other, mark)];
+
+            assert!(EdgeConstraint::new(EdgeFamily::Kropki, &layout, &marks, false).is_err());
+        }
+
+        let twice = [(0, 1, EdgeMark::White), (1, 0, EdgeMark::Black)];
+        assert!(EdgeConstraint::new(EdgeFamily::Kropki, &layout, &twice, false).is_err());
+    }
+
+    #[test]
+    fn sections() {
+        let layout = Layout::standard();
+        let section = Section {
+            name: "xv".to_string(),
+            lines: vec![
+                "x 0:0 0:1".to_string(),
+                "v 3:4 4:4".to_string(),
+                "negative".to_string(),
+            ],
+        };
+
+        let xv = from_section(&section, &layout).unwrap().unwrap();
+        assert_eq!(xv.to_section(), section);
+
+        let wrong = Section {
+            name: "kropki".to_string(),
+            lines: vec!["g 0:0 0:1".to_string()],
+        };
+        assert!(from_section(&wrong, &layout).unwrap().is_err());
+    }
+
+    #[test]
+    fn solution_of_another_size() {
+        let full = generate(&mut rand::rng()).unwrap();
+
+        let err =
+            EdgeConstraint::from_solution(EdgeFamily::Kropki, &Layout::for_size(4).unwrap(), &full)
+                .unwrap_err();
+
+        assert!(matches!(err, SabitaError::Dimension(_)));
+    }
+
+    #[test]
+    fn generate_and_solve() {
+        let layout = Layout::standard();
+
+        for family in [EdgeFamily::Kropki, EdgeFamily::XV] {
+            let full = generate(&mut rand::rng()).unwrap();
+            let edges = EdgeConstraint::from_solution(family, &layout, &full).unwrap();
+            let constraints: Vec<Arc<dyn Constraint>> = vec![Arc::new(edges)];
+
+            assert!(validate_with_constraints(&full, &layout, &constraints).is_ok());
+
+            let (puzzle, _) = remove_values_keeping_unique_with_constraints(
+                &full,
+                &layout,
+                &constraints,
+                60,
+                &mut rand::rng(),
+            )
+            .unwrap();
+
+            let mut grid = Grid::with_constraints(puzzle, layout.clone(), constraints).unwrap();
+            assert_eq!(grid.count_solutions(2), 1);
+
+            grid.solve().unwrap();
+            assert_eq!(grid.values, full);
+        }
+    }
+}
@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod killer {
+    use sabita::core::board::{mask_to_values, Board};
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::constraint::{from_section, Constraint};
+    use sabita::core::dlx::DancingLinks;
+    use sabita::core::file::Section;
+    use sabita::core::generator::generate_killer;
+    use sabita::core::grid::Grid;
+    use sabita::core::killer::{combinations, Cage, Killer};
+    use sabita::core::layout::Layout;
+    use sabita::core::transform::Transformation;
+    use sabita::core::validation::validate_with_constraints;
+    use sabita::SabitaError;
+
+    use crate::common::TempFile;
+
+    use std::{fs, sync::Arc};
+
+    ////////////////////
+
+    fn section(lines: Vec<&str>) -> Section {
+        Section {
+            name: "killer".to_string(),
+            lines: lines.iter().map(|line| line.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn sum_combinations() {
+        let all = 0b1_1111_1111;
+
+        assert_eq!(mask_to_values(combinations(all, 2, 3).unwrap()), vec![1, 2]);
+        assert_eq!(
+            mask_to_values(combinations(all, 2, 17).unwrap()),
+            vec![8, 9]
+        );
+        assert_eq!(
+            combinations(all, 3, 6).map(mask_to_values),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(combinations(all, 9, 45), Some(all));
+        assert!(combinations(all, 2, 2).is_none());
+        assert!(combinations(all, 2, 18).is_none());
+    }
+
+    #[test]
+    fn cage_pruning() {
+        let layout = Layout::standard();
+        let killer = Killer::new(&layout, vec![Cage::new(vec![0, 1], 4)]).unwrap();
+
+        let empty = Board::empty(&layout);
+        let candidates = killer.prune(&empty, 0, empty.all_candidates());
+        assert_eq!(mask_to_values(candidates), vec![1, 3]);
+
+        let mut values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        values[0][1] = 3;
+        let board = Board::new(&values).unwrap();
+
+        assert_eq!(
+            mask_to_values(killer.prune(&board, 0, board.all_candidates())),
+            vec![1]
+        );
+        assert_eq!(
+            killer.prune(&board, 9, board.all_candidates()),
+            board.all_candidates()
+        );
+    }
+
+    #[test]
+    fn invalid_cages() {
+        let layout = Layout::standard();
+
+        assert!(Killer::new(&layout, vec![Cage::new(vec![0, 1], 18)]).is_err());
+        assert!(Killer::new(&layout, vec![Cage::new(vec![], 0)]).is_err());
+        assert!(Killer::new(&layout, vec![Cage::new(vec![81], 5)]).is_err());
+        assert!(Killer::new(
+            &layout,
+            vec![Cage::new(vec![0, 1], 5), Cage::new(vec![1, 2], 5)]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn sections() {
+        let layout = Layout::standard();
+
+        let killer =
+            Killer::from_section(&section(vec!["15 0:0 0:1 1:0", "3 8:8"]), &layout).unwrap();
+        assert_eq!(killer.cages()[0], Cage::new(vec![0, 1, 9], 15));
+        assert_eq!(killer.cages()[1], Cage::new(vec![80], 3));
+        assert_eq!(killer.to_section().lines, vec!["15 0:0 0:1 1:0", "3 8:8"]);
+
+        assert!(Killer::from_section(&section(vec!["15 0:0 0-1"]), &layout).is_err());
+        assert!(Killer::from_section(&section(vec!["15 0:0 9:0"]), &layout).is_err());
+        assert!(from_section(&section(vec!["x 0:0"]), &layout)
+            .unwrap()
+            .is_err());
+    }
+
+    #[test]
+    fn generate_and_solve() {
+        let layout = Layout::standard();
+        let (puzzle, killer) = generate_killer(&layout, &mut rand::rng()).unwrap();
+
+        let constraints: Vec<Arc<dyn Constraint>> = vec![Arc::new(killer.clone())];
+        assert!(validate_with_constraints(&puzzle, &layout, &constraints).is_ok());
+
+        let nb_given = puzzle.iter().flatten().filter(|value| **value != 0).count();
+        assert!(nb_given < 10);
+
+        let covered: usize = killer.cages().iter().map(|cage| cage.cells().len()).sum();
+        assert_eq!(covered, 81);
+
+        let mut grid = Grid::with_constraints(puzzle, layout, constraints).unwrap();
+        assert_eq!(grid.count_solutions(2), 1);
+
+        let mut with_dlx = grid.clone();
+        with_dlx.solve_with(&DancingLinks).unwrap();
+        grid.solve().unwrap();
+
+        assert_eq!(with_dlx.values, grid.values);
+
+        for cage in killer.cages() {
+            let sum: u32 = cage
+                .cells()
+                .iter()
+                .map(|cell| u32::from(grid.values[cell / 9][cell % 9]))
+                .sum();
+
+            assert_eq!(sum, cage.sum());
+        }
+    }
+
+    #[test]
+    fn small_grids() {
+        for size in [4, 6] {
+            let grid =
+                Grid::generate_killer(Layout::for_size(size).unwrap(), &mut rand::rng()).unwrap();
+
+            assert_eq!(grid.count_solutions(2), 1);
+        }
+    }
+
+    #[test]
+    fn classic_features_unsupported() {
+        let grid = Grid::generate_killer(Layout::standard(), &mut rand::rng()).unwrap();
+
+        assert!(matches!(grid.rate(), Err(SabitaError::Unsupported(_))));
+        assert!(matches!(grid.canonical(), Err(SabitaError::Unsupported(_))));
+        assert!(matches!(
+            grid.fingerprint(),
+            Err(SabitaError::Unsupported(_))
+        ));
+        assert!(matches!(
+            grid.solve_logically(),
+            Err(SabitaError::Unsupported(_))
+        ));
+        assert!(matches!(
+            grid.transform(&Transformation::identity()),
+            Err(SabitaError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let grid = Grid::generate_killer(Layout::standard(), &mut rand::rng()).unwrap();
+
+        let file = TempFile::new("killer");
+        let path = file.path();
+
+        grid.dump_file(path.clone()).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let read_back = Grid::from_file(path.clone()).unwrap();
+
+        assert!(content.contains("[killer]"));
+        assert_eq!(read_back.values, grid.values);
+        assert_eq!(read_back.count_solutions(2), 1);
+    }
+}
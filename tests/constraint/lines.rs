@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod lines {
+    use sabita::core::board::{mask_to_values, Board};
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::constraint::{from_section, Constraint};
+    use sabita::core::dlx::DancingLinks;
+    use sabita::core::file::Section;
+    use sabita::core::grid::{Grid, GridValues};
+    use sabita::core::layout::Layout;
+    use sabita::core::line_constraint::{LineConstraint, LineKind};
+    use sabita::core::validation::validate_with_constraints;
+
+    use std::sync::Arc;
+
+    ////////////////////
+
+    fn line(kind: LineKind, cells: Vec<usize>) -> LineConstraint {
+        LineConstraint::new(kind, &Layout::standard(), vec![cells]).unwrap()
+    }
+
+    fn allowed(constraint: &LineConstraint, values: &GridValues, index: usize) -> Vec<u8> {
+        let board = Board::new(values).unwrap();
+
+        mask_to_values(constraint.prune(&board, index, board.all_candidates()))
+    }
+
+    fn empty() -> GridValues {
+        vec![vec![TO_BE_SOLVED; 9]; 9]
+    }
+
+    #[test]
+    fn thermometer() {
+        let thermo = line(LineKind::Thermometer, vec![0, 1, 2, 3]);
+        let mut values = empty();
+
+        assert_eq!(allowed(&thermo, &values, 0), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(allowed(&thermo, &values, 3), vec![4, 5, 6, 7, 8, 9]);
+
+        values[0][2] = 5;
+        assert_eq!(allowed(&thermo, &values, 1), vec![2, 3, 4]);
+        assert_eq!(allowed(&thermo, &values, 3), vec![6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn conflicting_boxes() {
+        let thermo = line(LineKind::Thermometer, vec![0, 1, 2, 3]);
+        let arrow = line(LineKind::Arrow, vec![9, 10, 11]);
+        let mut values = empty();
+        values[0][0] = 5;
+        values[0][2] = 6;
+        values[0][3] = 8;
+        values[1][0] = 9;
+        values[1][1] = 2;
+        values[1][2] = 3;
+        let board = Board::new(&values).unwrap();
+
+        assert_eq!(thermo.conflicting_boxes(&board, 0, 5), vec![2]);
+        assert_eq!(thermo.conflicting_boxes(&board, 3, 8), vec![]);
+        assert_eq!(arrow.conflicting_boxes(&board, 9, 9), vec![10, 11]);
+    }
+
+    #[test]
+    fn arrow() {
+        let arrow = line(LineKind::Arrow, vec![0, 1, 2]);
+        let mut values = empty();
+
+        assert_eq!(allowed(&arrow, &values, 0), vec![2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(allowed(&arrow, &values, 1), vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        values[0][0] = 7;
+        values[0][2] = 3;
+        assert_eq!(allowed(&arrow, &values, 1), vec![4]);
+
+        values[0][0] = TO_BE_SOLVED;
+        values[0][1] = 4;
+        assert_eq!(allowed(&arrow, &values, 0), vec![7]);
+    }
+
+    #[test]
+    fn palindrome() {
+        let palindrome = line(LineKind::Palindrome, vec![0, 10, 20, 30, 40]);
+        let mut values = empty();
+
+        values[1][1] = 6;
+        assert_eq!(allowed(&palindrome, &values, 30), vec![6]);
+        assert_eq!(allowed(&palindrome, &values, 20).len(), 9);
+    }
+
+    #[test]
+    fn whispers() {
+        let whispers = line(LineKind::Whispers, vec![0, 1, 2]);
+        let mut values = empty();
+
+        assert_eq!(allowed(&whispers, &values, 0), vec![1, 2, 3, 4, 6, 7, 8, 9]);
+
+        values[0][1] = 3;
+        assert_eq!(allowed(&whispers, &values, 0), vec![8, 9]);
+        assert_eq!(allowed(&whispers, &values, 2), vec![8, 9]);
+    }
+
+    #[test]
+    fn invalid_lines() {
+        let layout = Layout::standard();
+
+        for cells in [vec![0], vec![0, 2], vec![0, 1, 0], vec![80, 81]] {
+            assert!(LineConstraint::new(LineKind::Whispers, &layout, vec![cells]).is_err());
+        }
+
+        let too_long = (0..9).chain([17]).collect();
+        assert!(LineConstraint::new(LineKind::Thermometer, &layout, vec![too_long]).is_err());
+    }
+
+    #[test]
+    fn sections() {
+        let layout = Layout::standard();
+        let section = Section {
+            name: "thermo".to_string(),
+            lines: vec!["0:0 0:1 1:2".to_string(), "8:8 7:7".to_string()],
+        };
+
+        let thermo = from_section(&section, &layout).unwrap().unwrap();
+        assert_eq!(thermo.name(), "thermo");
+        assert_eq!(thermo.to_section(), section);
+
+        let wrong = Section {
+            name: "arrow".to_string(),
+            lines: vec!["0:0 0:x".to_string()],
+        };
+        assert!(from_section(&wrong, &layout).unwrap().is_err());
+    }
+
+    #[test]
+    fn generate_and_solve() {
+        let constraints: Vec<Arc<dyn Constraint>> = vec![
+            Arc::new(line(LineKind::Thermometer, vec![0, 1, 2, 3])),
+            Arc::new(line(LineKind::Arrow, vec![40, 50, 60])),
+            Arc::new(line(LineKind::Palindrome, vec![45, 55, 65])),
+            Arc::new(line(LineKind::Whispers, vec![8, 17, 26, 35])),
+        ];
+
+        let mut grid = Grid::generate_with_constraints(
+            Layout::standard(),
+            constraints,
+            Some(55),
+            &mut rand::rng(),
+        )
+        .unwrap();
+        assert_eq!(grid.count_solutions(2), 1);
+
+        let mut with_dlx = grid.clone();
+        with_dlx.solve_with(&DancingLinks).unwrap();
+        grid.solve().unwrap();
+
+        assert_eq!(with_dlx.values, grid.values);
+        assert!(validate_with_constraints(&grid.values, &grid.layout, &grid.constraints).is_ok());
+
+        let values = &grid.values;
+        assert!(values[0][0] < values[0][1] && values[0][2] < values[0][3]);
+        assert_eq!(values[4][4], values[5][5] + values[6][6]);
+        assert_eq!(values[5][0], values[7][2]);
+        assert!(values[0][8].abs_diff(values[1][8]) >= 5);
+    }
+}
@@ -0,0 +1,5 @@
+mod constraint;
+mod edges;
+mod killer;
+mod lines;
+mod variants;
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod variants {
+    use sabita::core::constraint::{
+        AntiKing, AntiKnight, Constraint, Diagonal, ExtraRegions, NonConsecutive, Parity,
+        ParityCells,
+    };
+    use sabita::core::dlx::DancingLinks;
+    use sabita::core::generator::generate_with_constraints;
+    use sabita::core::grid::Grid;
+    use sabita::core::layout::Layout;
+    use sabita::core::validation::validate_with_constraints;
+    use sabita::SabitaError;
+
+    use crate::common::TempFile;
+
+    use std::{fs, sync::Arc};
+
+    ////////////////////
+
+    fn check_variant(constraints: Vec<Arc<dyn Constraint>>) {
+        let layout = Layout::standard();
+
+        let full = generate_with_constraints(&layout, &constraints, &mut rand::rng()).unwrap();
+        assert!(validate_with_constraints(&full, &layout, &constraints).is_ok());
+
+        let mut grid =
+            Grid::generate_with_constraints(layout, constraints, Some(50), &mut rand::rng())
+                .unwrap();
+        assert_eq!(grid.count_solutions(2), 1);
+
+        let mut with_dlx = grid.clone();
+        with_dlx.solve_with(&DancingLinks).unwrap();
+        grid.solve().unwrap();
+
+        assert_eq!(with_dlx.values, grid.values);
+        assert!(validate_with_constraints(&grid.values, &grid.layout, &grid.constraints).is_ok());
+    }
+
+    #[test]
+    fn sudoku_x() {
+        check_variant(vec![Arc::new(Diagonal)]);
+    }
+
+    #[test]
+    fn anti_knight() {
+        check_variant(vec![Arc::new(AntiKnight)]);
+    }
+
+    #[test]
+    fn anti_king() {
+        check_variant(vec![Arc::new(AntiKing)]);
+    }
+
+    #[test]
+    fn non_consecutive() {
+        check_variant(vec![Arc::new(NonConsecutive)]);
+    }
+
+    #[test]
+    fn windoku() {
+        check_variant(vec![Arc::new(ExtraRegions::windoku())]);
+    }
+
+    #[test]
+    fn parity() {
+        let cells = (0..9).map(|line| (line * 10, Parity::Even)).collect();
+
+        check_variant(vec![Arc::new(ParityCells::new(9, cells))]);
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let constraints: Vec<Arc<dyn Constraint>> =
+            vec![Arc::new(Diagonal), Arc::new(ExtraRegions::windoku())];
+        let grid = Grid::generate_with_constraints(
+            Layout::standard(),
+            constraints,
+            Some(50),
+            &mut rand::rng(),
+        )
+        .unwrap();
+
+        let file = TempFile::new("variant");
+        let path = file.path();
+
+        grid.dump_file(path.clone()).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let read_back = Grid::from_file(path.clone()).unwrap();
+
+        assert!(content.contains("[diagonal]"));
+        assert!(content.contains("[extra-regions]"));
+        assert_eq!(read_back.values, grid.values);
+        assert_eq!(read_back.constraints.len(), 2);
+        assert_eq!(read_back.count_solutions(2), 1);
+    }
+
+    #[test]
+    fn rating_needs_a_classic_grid() {
+        let grid = Grid::generate_with_constraints(
+            Layout::standard(),
+            vec![Arc::new(Diagonal)],
+            Some(40),
+            &mut rand::rng(),
+        )
+        .unwrap();
+
+        assert!(matches!(grid.rate(), Err(SabitaError::Unsupported(_))));
+    }
+}
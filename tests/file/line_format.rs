@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod line_format {
+    use sabita::assets::full_grid::GRID_VALUES_1;
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::file::{
+        parse_line, read, read_collection, read_collection_lines, values_to_line, write,
+        write_collection, Format,
+    };
+    use sabita::core::grid::Grid;
+    use sabita::core::layout::Layout;
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+    use sabita::SabitaError;
+
+    use crate::common::TempFile;
+
+    use std::fs;
+
+    ////////////////////
+
+    fn puzzle() -> Vec<Vec<u8>> {
+        let mut values = grid_values_array_to_vec(GRID_VALUES_1);
+        values[0][0] = TO_BE_SOLVED;
+        values[4][4] = TO_BE_SOLVED;
+        values[8][8] = TO_BE_SOLVED;
+
+        values
+    }
+
+    #[test]
+    fn line_round_trip() {
+        let values = puzzle();
+        let line = values_to_line(&values);
+
+        assert_eq!(line.len(), 81);
+        assert!(line.starts_with('.'));
+        assert_eq!(parse_line(&line).unwrap(), values);
+        assert_eq!(parse_line(&line.replace('.', "0")).unwrap(), values);
+    }
+
+    #[test]
+    fn wrong_lines() {
+        assert!(matches!(parse_line("123"), Err(SabitaError::Dimension(_))));
+        assert!(matches!(
+            parse_line(&"?".repeat(81)),
+            Err(SabitaError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn larger_grid_line() {
+        let layout = Layout::for_size(16).unwrap();
+        let grid = Grid::generate_with_layout(layout, None, &mut rand::rng()).unwrap();
+
+        let line = grid.to_line();
+
+        assert_eq!(line.len(), 256);
+        assert_eq!(parse_line(&line).unwrap(), grid.values);
+    }
+
+    #[test]
+    fn format_detected() {
+        let values = puzzle();
+        let line_file = TempFile::new("line_detected");
+        let line_path = line_file.path();
+        let grid_file = TempFile::new("grid_detected");
+        let grid_path = grid_file.path();
+
+        write_collection(line_path.clone(), std::slice::from_ref(&values)).unwrap();
+        write(grid_path.clone(), values.clone()).unwrap();
+
+        let from_line = read(line_path.clone()).unwrap();
+        let from_grid = read(grid_path.clone()).unwrap();
+        let grid = Grid::from_file(line_path.clone()).unwrap();
+
+        assert_eq!(from_line, values);
+        assert_eq!(from_grid, values);
+        assert_eq!(grid.values, values);
+    }
+
+    #[test]
+    fn collections() {
+        let first = puzzle();
+        let second = grid_values_array_to_vec(GRID_VALUES_1);
+
+        let file = TempFile::new("collection");
+        let path = file.path();
+        let content = format!(
+            "# Two puzzles\n{}\n\n{} rated 1.2\n",
+            values_to_line(&first),
+            values_to_line(&second)
+        );
+        fs::write(&path, content).unwrap();
+
+        let collection = read_collection(path.clone()).unwrap();
+        let grids = Grid::collection_from_file(path.clone()).unwrap();
+        let single = Grid::from_file(path.clone());
+
+        assert_eq!(collection, vec![first, second]);
+        assert_eq!(grids.len(), 2);
+        assert!(matches!(single, Err(SabitaError::Parse(_))));
+    }
+
+    #[test]
+    fn collection_error_line_number() {
+        let file = TempFile::new("collection_error");
+        let path = file.path();
+        let content = format!(
+            "# Header\n{}\n\n{}x\n",
+            values_to_line(&puzzle()),
+            values_to_line(&puzzle())
+        );
+        fs::write(&path, content).unwrap();
+
+        let Err(SabitaError::Dimension(reason)) = read_collection(path.clone()) else {
+            panic!("A wrong line should be a dimension error");
+        };
+        assert!(reason.starts_with("line 4: "));
+        assert_eq!(read_collection_lines(path.clone()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn dump_as_line() {
+        let grid = Grid::new(puzzle()).unwrap();
+        let file = TempFile::new("dump_line");
+        let path = file.path();
+
+        grid.dump_file_as(path.clone(), Format::Line).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+
+        assert_eq!(content, format!("{}\n", grid.to_line()));
+    }
+
+    #[test]
+    fn jigsaw_not_written_as_line() {
+        let regions = vec![
+            vec![0, 0, 0, 1],
+            vec![0, 2, 1, 1],
+            vec![2, 2, 3, 1],
+            vec![2, 3, 3, 3],
+        ];
+        let layout = Layout::jigsaw(&regions).unwrap();
+        let grid = Grid::with_layout(vec![vec![TO_BE_SOLVED; 4]; 4], layout).unwrap();
+
+        assert!(matches!(
+            grid.dump_file_as(TempFile::new("jigsaw_line").path(), Format::Line),
+            Err(SabitaError::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1 @@
+mod line_format;
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod daily {
+    use sabita::core::date::Date;
+    use sabita::core::file::values_to_line;
+    use sabita::core::generator::{daily, daily_seed};
+    use sabita::core::rating::{rate, Level};
+    use sabita::core::solver::has_unique_solution;
+
+    ////////////////////
+
+    #[test]
+    fn parse_date() {
+        let date = Date::parse("2024-02-29").unwrap();
+
+        assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 29));
+        assert_eq!(date.to_string(), "2024-02-29");
+    }
+
+    #[test]
+    fn reject_invalid_dates() {
+        for text in [
+            "2023-02-29",
+            "2024-13-01",
+            "2024-04-31",
+            "2024-1-01",
+            "24-01-01",
+            "today",
+        ] {
+            assert!(Date::parse(text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn seed_follows_date() {
+        let date = Date::new(2024, 12, 25).unwrap();
+
+        assert_eq!(daily_seed(&date), 20241225);
+        assert_ne!(
+            daily_seed(&date),
+            daily_seed(&Date::new(2024, 12, 26).unwrap())
+        );
+    }
+
+    #[test]
+    fn same_day_same_puzzle() {
+        let date = Date::parse("2024-12-25").unwrap();
+
+        let first = daily(&date, Level::Medium).unwrap();
+        let second = daily(&date, Level::Medium).unwrap();
+
+        assert_eq!(first, second);
+        assert!(has_unique_solution(&first).unwrap());
+        assert_eq!(rate(&first).unwrap().level, Level::Medium);
+
+        let next_day = daily(&Date::parse("2024-12-26").unwrap(), Level::Medium).unwrap();
+        assert_ne!(first, next_day);
+    }
+
+    /// Clients compute the daily puzzle offline, any change of the random generator must break this
+    #[test]
+    fn golden_puzzle() {
+        let date = Date::parse("2024-12-25").unwrap();
+
+        assert_eq!(
+            values_to_line(&daily(&date, Level::Medium).unwrap()),
+            "............37..9.....467..6.41...3..3.8.52......3..8...3...9.871...9....5.....62"
+        );
+        assert_eq!(
+            values_to_line(&daily(&date, Level::Hard).unwrap()),
+            "..6.....3...4.3..93.4..6.5.4...5.8..5...9..1......86........7..2....1.....1.74..6"
+        );
+    }
+
+    #[test]
+    fn today_is_valid() {
+        let today = Date::today();
+
+        assert!(Date::new(today.year(), today.month(), today.day()).is_ok());
+        assert!(today.year() >= 2024);
+    }
+}
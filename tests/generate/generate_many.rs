@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod generate_many {
+    use sabita::core::canonical::fingerprint;
+    use sabita::core::file::read_collection;
+    use sabita::core::generator::generate_many;
+    use sabita::core::solver::has_unique_solution;
+    use sabita::SabitaError;
+
+    use crate::common::TempFile;
+
+    use std::collections::HashSet;
+    use std::fs;
+
+    ////////////////////
+
+    #[test]
+    fn distinct_unique_puzzles() {
+        let file = TempFile::new("generate_many");
+        let path = file.path();
+
+        let report = generate_many(30, 4, Some(45), None, 42, path.clone()).unwrap();
+        let puzzles = read_collection(path.clone()).unwrap();
+
+        assert_eq!(report.nb_duplicates, 0);
+        assert_eq!(puzzles.len(), 30);
+        assert!(puzzles
+            .iter()
+            .all(|puzzle| has_unique_solution(puzzle).unwrap()));
+
+        let fingerprints: HashSet<String> = puzzles.iter().map(fingerprint).collect();
+        assert_eq!(fingerprints.len(), puzzles.len());
+    }
+
+    #[test]
+    fn same_seed_single_thread_same_file() {
+        let first_file = TempFile::new("generate_many_first");
+        let first_path = first_file.path();
+        let second_file = TempFile::new("generate_many_second");
+        let second_path = second_file.path();
+
+        generate_many(10, 1, Some(40), None, 7, first_path.clone()).unwrap();
+        generate_many(10, 1, Some(40), None, 7, second_path.clone()).unwrap();
+        let first = fs::read_to_string(&first_path).unwrap();
+        let second = fs::read_to_string(&second_path).unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn failed_attempts_skipped() {
+        let file = TempFile::new("generate_many_skipped");
+        let path = file.path();
+
+        // Many attempts can not remove that much values while keeping a single solution
+        let report = generate_many(3, 1, Some(59), None, 1, path.clone()).unwrap();
+        let puzzles = read_collection(path.clone()).unwrap();
+
+        assert!(report.nb_failed > 0);
+        assert_eq!(puzzles.len(), 3);
+        assert!(puzzles
+            .iter()
+            .all(|puzzle| has_unique_solution(puzzle).unwrap()));
+    }
+
+    #[test]
+    fn failing_generation_stops_every_thread() {
+        let file = TempFile::new("generate_many_failing");
+        let path = file.path();
+
+        let err = generate_many(5, 3, Some(81), None, 1, path.clone()).unwrap_err();
+
+        assert!(matches!(err, SabitaError::Generation(_)));
+    }
+
+    #[test]
+    fn unwritable_file() {
+        let file = TempFile::new("generate_many_missing_dir/puzzles");
+        let path = file.path();
+
+        let err = generate_many(1, 1, None, None, 1, path).unwrap_err();
+
+        assert!(matches!(err, SabitaError::Io { .. }));
+    }
+}
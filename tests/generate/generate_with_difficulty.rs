@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod generate_with_difficulty {
+    use sabita::core::generator::generate_with_difficulty;
+    use sabita::core::grid::Grid;
+    use sabita::core::rating::{rate, Level};
+    use sabita::core::solver::has_unique_solution;
+
+    ////////////////////
+
+    fn generation_test(level: Level) {
+        let values = generate_with_difficulty(level, &mut rand::rng()).unwrap();
+
+        assert!(
+            has_unique_solution(&values).unwrap(),
+            "Grid has several solutions"
+        );
+        assert_eq!(rate(&values).unwrap().level, level, "Wrong level");
+    }
+
+    #[test]
+    fn easy() {
+        generation_test(Level::Easy);
+    }
+
+    #[test]
+    fn medium() {
+        generation_test(Level::Medium);
+    }
+
+    #[test]
+    fn hard() {
+        generation_test(Level::Hard);
+    }
+
+    #[test]
+    fn expert() {
+        generation_test(Level::Expert);
+    }
+
+    #[test]
+    fn from_grid() {
+        let grid = Grid::generate(None, Some(Level::Medium), &mut rand::rng()).unwrap();
+
+        assert_eq!(grid.rate().unwrap().level, Level::Medium);
+        assert_eq!(grid.count_solutions(2), 1);
+    }
+}
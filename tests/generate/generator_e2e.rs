@@ -1,9 +1,10 @@
 #[cfg(test)]
 mod generator_tests {
     use sabita::assets::full_grid::GRID_VALUES_1;
-    use sabita::core::constants::LENGTH_DIMENSION;
+    use sabita::core::constants::{LENGTH_DIMENSION, TO_BE_SOLVED};
     use sabita::core::generator::generate;
     use sabita::core::grid::GridValues;
+    use sabita::core::validation::validate;
     use sabita::utils::grid_utils::grid_values_array_to_vec;
 
     ////////////////////
@@ -12,7 +13,7 @@ mod generator_tests {
     fn generation() {
         let original: GridValues = grid_values_array_to_vec(GRID_VALUES_1);
 
-        let generated = generate().unwrap();
+        let generated = generate(&mut rand::rng()).unwrap();
 
         assert_ne!(original, generated, "Sudokus should be different");
         assert_eq!(
@@ -29,4 +30,28 @@ mod generator_tests {
             );
         }
     }
+
+    #[test]
+    fn generated_grid_is_complete_and_valid() {
+        let generated = generate(&mut rand::rng()).unwrap();
+
+        assert!(validate(&generated).is_ok(), "Grid isn't valid");
+        assert!(
+            !generated.iter().flatten().any(|&val| val == TO_BE_SOLVED),
+            "Grid isn't complete"
+        );
+    }
+
+    #[test]
+    fn generated_grids_differ() {
+        let generated: Vec<GridValues> = (0..5)
+            .map(|_| generate(&mut rand::rng()).unwrap())
+            .collect();
+
+        let first_lines_differ = generated.windows(2).any(|pair| pair[0][0] != pair[1][0]);
+
+        // Starting from an empty board, no pattern of a seed grid should be kept
+        assert!(first_lines_differ, "Every grid starts with the same line");
+        assert_ne!(generated[0], generated[1], "Sudokus should be different");
+    }
 }
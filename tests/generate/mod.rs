@@ -1,2 +1,7 @@
+mod daily;
+mod generate_many;
+mod generate_with_difficulty;
 mod generator_e2e;
 mod permute_values;
+mod remove_values_keeping_unique;
+mod seed;
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod remove_values_keeping_unique {
+    use sabita::assets::full_grid::GRID_VALUES_1;
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::generator::remove_values_keeping_unique;
+    use sabita::core::grid::Grid;
+    use sabita::core::solver::has_unique_solution;
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+
+    ////////////////////
+
+    fn count_missing(values: &[Vec<u8>]) -> usize {
+        values
+            .iter()
+            .flatten()
+            .filter(|&&val| val == TO_BE_SOLVED)
+            .count()
+    }
+
+    #[test]
+    fn keeps_single_solution() {
+        let original = grid_values_array_to_vec(GRID_VALUES_1);
+
+        let (values, removed) =
+            remove_values_keeping_unique(&original, 45, &mut rand::rng()).unwrap();
+
+        assert_eq!(removed.len(), 45, "Bad number of removed locations");
+        assert_eq!(count_missing(&values), 45, "Bad number of missing boxes");
+        assert!(
+            has_unique_solution(&values).unwrap(),
+            "Grid has several solutions"
+        );
+    }
+
+    #[test]
+    fn below_minimum_provided() {
+        let original = grid_values_array_to_vec(GRID_VALUES_1);
+
+        match remove_values_keeping_unique(&original, 70, &mut rand::rng()) {
+            Ok(_) => panic!("Should have thrown error"),
+            Err(err) => assert_eq!(
+                err.to_string(),
+                "Could not remove 70 values while keeping a single solution"
+            ),
+        }
+    }
+
+    #[test]
+    fn remove_full_grid() {
+        let original = grid_values_array_to_vec(GRID_VALUES_1);
+
+        assert!(remove_values_keeping_unique(&original, 81, &mut rand::rng()).is_err());
+    }
+
+    #[test]
+    fn several_solutions_detected() {
+        let mut values = grid_values_array_to_vec(GRID_VALUES_1);
+
+        // 5 and 4 can swap places on both lines, leaving two ways to complete the grid
+        values[0][6] = TO_BE_SOLVED;
+        values[0][8] = TO_BE_SOLVED;
+        values[4][6] = TO_BE_SOLVED;
+        values[4][8] = TO_BE_SOLVED;
+
+        assert!(
+            !has_unique_solution(&values).unwrap(),
+            "Grid should not be unique"
+        );
+    }
+
+    #[test]
+    fn generated_grid_is_unique() {
+        let grid = Grid::generate(Some(50), None, &mut rand::rng()).unwrap();
+
+        assert_eq!(count_missing(&grid.get_values()), 50);
+        assert!(has_unique_solution(&grid.get_values()).unwrap());
+    }
+}
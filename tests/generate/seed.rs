@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod seed {
+    use sabita::assets::full_grid::GRID_VALUES_1;
+    use sabita::core::generator::{
+        generate, generate_with_difficulty, remove_random_values, remove_values_keeping_unique,
+        seeded_rng,
+    };
+    use sabita::core::grid::Grid;
+    use sabita::core::rating::Level;
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+
+    ////////////////////
+
+    #[test]
+    fn same_seed_same_full_grid() {
+        let first = generate(&mut seeded_rng(42)).unwrap();
+        let second = generate(&mut seeded_rng(42)).unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, generate(&mut seeded_rng(43)).unwrap());
+    }
+
+    #[test]
+    fn same_seed_same_removals() {
+        let values = grid_values_array_to_vec(GRID_VALUES_1);
+
+        let (first, first_removed) = remove_random_values(&values, 40, &mut seeded_rng(7)).unwrap();
+        let (second, second_removed) =
+            remove_random_values(&values, 40, &mut seeded_rng(7)).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first_removed, second_removed);
+
+        let (first, _) = remove_values_keeping_unique(&values, 50, &mut seeded_rng(7)).unwrap();
+        let (second, _) = remove_values_keeping_unique(&values, 50, &mut seeded_rng(7)).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        let first = Grid::generate(Some(50), None, &mut seeded_rng(2024)).unwrap();
+        let second = Grid::generate(Some(50), None, &mut seeded_rng(2024)).unwrap();
+        assert_eq!(first.values, second.values);
+
+        let first = generate_with_difficulty(Level::Medium, &mut seeded_rng(2024)).unwrap();
+        let second = generate_with_difficulty(Level::Medium, &mut seeded_rng(2024)).unwrap();
+        assert_eq!(first, second);
+    }
+}
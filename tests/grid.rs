@@ -1,9 +1,17 @@
+mod common;
+
 #[cfg(test)]
 mod grid_tests {
     use sabita::assets::full_grid::{GRID_VALUES_1, GRID_VALUES_2};
     use sabita::core::constants::TO_BE_SOLVED;
     use sabita::core::grid::Grid;
+    use sabita::core::rating::Level;
     use sabita::utils::grid_utils::grid_values_array_to_vec;
+    use sabita::SabitaError;
+
+    use crate::common::TempFile;
+
+    use std::fs;
 
     ////////////////////
     // Valid grid
@@ -20,7 +28,8 @@ mod grid_tests {
             vec![1, 4, 9, 6, 7, 3, 2, 5, 8],
             vec![5, 3, 8, 1, 4, 2, 9, 6, 7],
             vec![7, 2, 6, 8, 9, 5, 3, 4, 1],
-        ]);
+        ])
+        .unwrap();
     }
 
     #[test]
@@ -35,22 +44,23 @@ mod grid_tests {
             vec![1, 4, 9, 6, 7, 3, TO_BE_SOLVED, 5, 8],
             vec![TO_BE_SOLVED, 3, 8, 1, 4, 2, 9, 6, 7],
             vec![7, 2, 6, TO_BE_SOLVED, 9, 5, 3, 4, 1],
-        ]);
+        ])
+        .unwrap();
     }
 
     ////////////////////
     // Invalid length
 
     #[test]
-    #[should_panic(expected = "Wrong number of lines: 0")]
     fn empty_grid() {
-        Grid::new(vec![]);
+        let err = Grid::new(vec![]).unwrap_err();
+
+        assert!(err.to_string().contains("Wrong number of lines: 0"));
     }
 
     #[test]
-    #[should_panic(expected = "Line index 0 has a different number of columns than 9")]
     fn too_few_columns0() {
-        Grid::new(vec![
+        let err = Grid::new(vec![
             vec![1, 2, 3, 4, 5, 6, 7, 8],
             vec![2, 3, 4, 5, 6, 7, 8, 9],
             vec![3, 4, 5, 6, 7, 8, 9, 1],
@@ -60,13 +70,17 @@ mod grid_tests {
             vec![7, 8, 9, 1, 2, 3, 4, 5],
             vec![8, 9, 1, 2, 3, 4, 5, 6],
             vec![9, 1, 2, 3, 4, 5, 6, 7],
-        ]);
+        ])
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Line index 0 has a different number of columns than 9"));
     }
 
     #[test]
-    #[should_panic(expected = "Line index 5 has a different number of columns than 9")]
     fn too_few_columns5() {
-        Grid::new(vec![
+        let err = Grid::new(vec![
             vec![3, 9, 1, 2, 8, 6, 5, 7, 4],
             vec![4, 8, 7, 3, 5, 9, 1, 2, 6],
             vec![6, 5, 2, 7, 1, 4, 8, 3, 9],
@@ -76,13 +90,17 @@ mod grid_tests {
             vec![1, 4, 9, 6, 7, 3, 2, 5, 8],
             vec![5, 3, 8, 1, 4, 2, 9, 6, 7],
             vec![7, 2, 6, 8, 9, 5, 3, 4, 1],
-        ]);
+        ])
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Line index 5 has a different number of columns than 9"));
     }
 
     #[test]
-    #[should_panic(expected = "Wrong number of lines: 8")]
     fn too_few_lines() {
-        Grid::new(vec![
+        let err = Grid::new(vec![
             vec![1, 2, 3, 4, 5, 6, 7, 8, TO_BE_SOLVED],
             vec![2, 3, 4, 5, 6, 7, 8, 9, TO_BE_SOLVED],
             vec![3, 4, 5, 6, 7, 8, 9, 1, TO_BE_SOLVED],
@@ -91,16 +109,18 @@ mod grid_tests {
             vec![6, 7, 8, 9, 1, 2, 3, 4, TO_BE_SOLVED],
             vec![7, 8, 9, 1, 2, 3, 4, 5, TO_BE_SOLVED],
             vec![8, 9, 1, 2, 3, 4, 5, 6, TO_BE_SOLVED],
-        ]);
+        ])
+        .unwrap_err();
+
+        assert!(err.to_string().contains("Wrong number of lines: 8"));
     }
 
     ////////////////////
     // Line checks
 
     #[test]
-    #[should_panic(expected = "Line index 0 is not valid, duplicate value 3")]
     fn invalid_line0() {
-        Grid::new(vec![
+        let err = Grid::new(vec![
             vec![3, 3, 1, 2, 8, 6, 5, 7, 4],
             vec![4, 8, 7, 3, 5, 9, 1, 2, 6],
             vec![6, 5, 2, 7, 1, 4, 8, 3, 9],
@@ -110,13 +130,17 @@ mod grid_tests {
             vec![1, 4, 9, 6, 7, 3, 2, 5, 8],
             vec![5, 3, 8, 1, 4, 2, 9, 6, 7],
             vec![7, 2, 6, 8, 9, 5, 3, 4, 1],
-        ]);
+        ])
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Line index 0 is not valid, duplicate value 3"));
     }
 
     #[test]
-    #[should_panic(expected = "Line index 7 is not valid, duplicate value 6")]
     fn invalid_line7() {
-        Grid::new(vec![
+        let err = Grid::new(vec![
             vec![3, 9, 1, 2, 8, 6, 5, 7, 4],
             vec![4, 8, 7, 3, 5, 9, 1, 2, TO_BE_SOLVED],
             vec![6, 5, 2, 7, 1, 4, 8, 3, 9],
@@ -126,16 +150,20 @@ mod grid_tests {
             vec![1, 4, 9, 6, 7, 3, 2, 5, 8],
             vec![5, 3, 8, 1, 4, 2, 9, 6, 6],
             vec![7, 2, 6, 8, 9, 5, 3, 4, 1],
-        ]);
+        ])
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Line index 7 is not valid, duplicate value 6"));
     }
 
     ////////////////////
     // Column checks
 
     #[test]
-    #[should_panic(expected = "Column index 1 is not valid, duplicate value 5")]
     fn invalid_column1() {
-        Grid::new(vec![
+        let err = Grid::new(vec![
             vec![3, 9, 1, 2, 8, 6, 5, 7, 4],
             vec![4, 8, 7, 3, 5, 9, 1, 2, 6],
             vec![6, 5, 2, 7, 1, 4, 8, 3, 9],
@@ -145,16 +173,20 @@ mod grid_tests {
             vec![1, 4, 9, 6, 7, 3, 2, 5, 8],
             vec![5, 5, 8, 1, 4, 2, 9, 6, 7], // Here
             vec![7, 2, 6, 8, 9, 5, 3, 4, 1],
-        ]);
+        ])
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Column index 1 is not valid, duplicate value 5"));
     }
 
     ////////////////////
     // Region checks
 
     #[test]
-    #[should_panic(expected = "Region index 0 is not valid, duplicate value 2")]
     fn invalid_region0() {
-        Grid::new(vec![
+        let err = Grid::new(vec![
             vec![1, 2, 3, 4, 5, 6, 7, 8, TO_BE_SOLVED],
             vec![2, 3, 4, 5, 6, 7, 8, 9, TO_BE_SOLVED],
             vec![3, 4, 5, 6, 7, 8, 9, 1, TO_BE_SOLVED],
@@ -164,13 +196,17 @@ mod grid_tests {
             vec![7, 8, 9, 1, 2, 3, 4, 5, TO_BE_SOLVED],
             vec![8, 9, 1, 2, 3, 4, 5, 6, TO_BE_SOLVED],
             vec![9, 1, 2, 3, 4, 5, 6, 7, TO_BE_SOLVED],
-        ]);
+        ])
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Region index 0 is not valid, duplicate value 2"));
     }
 
     #[test]
-    #[should_panic(expected = "Region index 8 is not valid, duplicate value 5")]
     fn invalid_region8() {
-        Grid::new(vec![
+        let err = Grid::new(vec![
             vec![3, 9, 1, 2, 8, 6, TO_BE_SOLVED, 7, 4],
             vec![4, 8, 7, 3, 5, 9, 1, 2, 6],
             vec![6, TO_BE_SOLVED, 2, 7, 1, 4, 8, 3, 9],
@@ -180,38 +216,50 @@ mod grid_tests {
             vec![1, 4, 9, 6, 7, 3, 2, 5, 8],
             vec![TO_BE_SOLVED, 3, 8, 1, 4, 2, 5, 9, 7],
             vec![7, 5, 6, 8, 9, TO_BE_SOLVED, 3, 4, 1],
-        ]);
+        ])
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Region index 8 is not valid, duplicate value 5"));
     }
 
     ////////////////////
     // Remove random values
 
     #[test]
-    #[should_panic(expected = "Can not remove that much values")]
     fn invalid_remove_random_value_full_grid() {
         let values = grid_values_array_to_vec(GRID_VALUES_2);
-        let mut grid = Grid::new(values.clone());
+        let mut grid = Grid::new(values.clone()).unwrap();
 
-        grid.remove_random_values(81);
+        assert!(matches!(
+            grid.remove_random_values(81, &mut rand::rng()),
+            Err(SabitaError::Generation(_))
+        ));
+        assert_eq!(grid.get_values(), values);
     }
 
     #[test]
-    #[should_panic(expected = "Can not remove that much values")]
     fn invalid_remove_random_value_above() {
         let values = grid_values_array_to_vec(GRID_VALUES_2);
-        let mut grid = Grid::new(values.clone());
+        let mut grid = Grid::new(values.clone()).unwrap();
 
-        grid.remove_random_values(90);
+        assert!(matches!(
+            grid.remove_random_values(90, &mut rand::rng()),
+            Err(SabitaError::Generation(_))
+        ));
+        assert_eq!(grid.get_values(), values);
     }
 
     #[test]
     fn remove_1_random_value() {
         let values = grid_values_array_to_vec(GRID_VALUES_1);
-        let mut grid = Grid::new(values.clone());
+        let mut grid = Grid::new(values.clone()).unwrap();
 
         let nb_to_remove = 1;
 
-        grid.remove_random_values(nb_to_remove);
+        grid.remove_random_values(nb_to_remove, &mut rand::rng())
+            .unwrap();
         let missing = grid.locate_missing_box();
 
         assert_eq!(missing.len(), nb_to_remove as usize);
@@ -221,11 +269,12 @@ mod grid_tests {
     #[test]
     fn remove_20_random_value() {
         let values = grid_values_array_to_vec(GRID_VALUES_2);
-        let mut grid = Grid::new(values.clone());
+        let mut grid = Grid::new(values.clone()).unwrap();
 
         let nb_to_remove = 20;
 
-        grid.remove_random_values(nb_to_remove);
+        grid.remove_random_values(nb_to_remove, &mut rand::rng())
+            .unwrap();
         let missing = grid.locate_missing_box();
 
         assert_eq!(missing.len(), nb_to_remove as usize);
@@ -235,14 +284,112 @@ mod grid_tests {
     #[test]
     fn remove_80_random_value() {
         let values = grid_values_array_to_vec(GRID_VALUES_2);
-        let mut grid = Grid::new(values.clone());
+        let mut grid = Grid::new(values.clone()).unwrap();
 
         let nb_to_remove = 80;
 
-        grid.remove_random_values(nb_to_remove);
+        grid.remove_random_values(nb_to_remove, &mut rand::rng())
+            .unwrap();
         let missing = grid.locate_missing_box();
 
         assert_eq!(missing.len(), nb_to_remove as usize);
         assert_ne!(values, grid.get_values());
     }
+
+    ////////////////////
+    // Errors
+
+    fn temp_file(name: &str, content: &str) -> TempFile {
+        let file = TempFile::new(name);
+        fs::write(file.path(), content).unwrap();
+
+        file
+    }
+
+    #[test]
+    fn conflicting_values() {
+        let mut values = grid_values_array_to_vec(GRID_VALUES_1);
+        values[0][0] = values[0][1];
+
+        assert!(matches!(Grid::new(values), Err(SabitaError::Conflict(_))));
+    }
+
+    #[test]
+    fn missing_file() {
+        let err = Grid::from_file("does/not/exist.txt".to_string()).unwrap_err();
+
+        assert!(matches!(err, SabitaError::Io { .. }));
+    }
+
+    #[test]
+    fn wrong_value_in_file() {
+        let file = temp_file("wrong_value", "1,2,x?\n");
+        let err = Grid::from_file(file.path()).unwrap_err();
+
+        assert!(matches!(err, SabitaError::Parse(_)));
+    }
+
+    #[test]
+    fn unknown_section_in_file() {
+        let mut content = String::new();
+        for line in grid_values_array_to_vec(GRID_VALUES_1) {
+            let line: Vec<String> = line.iter().map(|value| value.to_string()).collect();
+            content += &format!("{}\n", line.join(","));
+        }
+        content += "\n[unknown]\n";
+
+        let file = temp_file("unknown_section", &content);
+        let err = Grid::from_file(file.path()).unwrap_err();
+
+        assert!(err.to_string().contains("unknown section [unknown]"));
+    }
+
+    #[test]
+    fn unsolvable_grid() {
+        let mut values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        values[0] = vec![1, 2, 3, 4, 5, 6, 7, 8, TO_BE_SOLVED];
+        values[1][8] = 9;
+
+        let mut grid = Grid::new(values).unwrap();
+
+        assert!(matches!(grid.solve(), Err(SabitaError::Unsolvable)));
+    }
+
+    #[test]
+    fn too_many_values_to_remove() {
+        let err = Grid::generate(Some(81), None, &mut rand::rng()).unwrap_err();
+
+        assert!(matches!(err, SabitaError::Generation(_)));
+    }
+
+    #[test]
+    fn removals_and_difficulty_together() {
+        let err = Grid::generate(Some(40), Some(Level::Easy), &mut rand::rng()).unwrap_err();
+
+        assert!(matches!(err, SabitaError::Generation(_)));
+    }
+
+    #[test]
+    fn conflicts_in_file() {
+        let mut values = grid_values_array_to_vec(GRID_VALUES_1);
+        values[0][0] = values[0][1];
+
+        let content: Vec<String> = values
+            .iter()
+            .map(|line| {
+                let line: Vec<String> = line.iter().map(|value| value.to_string()).collect();
+                line.join(",")
+            })
+            .collect();
+
+        let file = temp_file("conflicts", &content.join("\n"));
+        let conflicts = Grid::conflicts_in_file(file.path()).unwrap();
+
+        // The line, the column and the region of the changed box
+        assert_eq!(conflicts.len(), 3);
+        assert!(conflicts.iter().all(|err| err
+            .cells()
+            .iter()
+            .any(|cell| cell.line == 0 && cell.column == 0)));
+    }
 }
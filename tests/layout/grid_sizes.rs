@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod grid_sizes {
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::dlx::DancingLinks;
+    use sabita::core::file::{read, write};
+    use sabita::core::generator::{generate_with_layout, remove_values_keeping_unique};
+    use sabita::core::grid::Grid;
+    use sabita::core::layout::Layout;
+    use sabita::core::solver::{has_unique_solution, solve, Solver};
+    use sabita::core::validation::validate;
+    use sabita::SabitaError;
+
+    use crate::common::TempFile;
+
+    use std::fs;
+
+    ////////////////////
+
+    #[test]
+    fn generated_grids_are_valid() {
+        for size in [4, 6, 12, 16] {
+            let layout = Layout::for_size(size).unwrap();
+            let values = generate_with_layout(&layout, &mut rand::rng()).unwrap();
+
+            assert_eq!(values.len(), size);
+            assert!(validate(&values).is_ok(), "{layout}");
+            assert!(!values.iter().flatten().any(|value| *value == TO_BE_SOLVED));
+        }
+    }
+
+    #[test]
+    fn solve_other_sizes() {
+        for size in [4, 6, 12, 16] {
+            let layout = Layout::for_size(size).unwrap();
+            let full = generate_with_layout(&layout, &mut rand::rng()).unwrap();
+            let nb_to_remove = (size * size / 3) as u16;
+
+            let (puzzle, _) =
+                remove_values_keeping_unique(&full, nb_to_remove, &mut rand::rng()).unwrap();
+
+            assert!(has_unique_solution(&puzzle).unwrap(), "{layout}");
+            assert_eq!(solve(&puzzle).unwrap(), full, "{layout}");
+            assert_eq!(DancingLinks.solve(&puzzle).unwrap(), full, "{layout}");
+        }
+    }
+
+    #[test]
+    fn generate_grid_with_layout() {
+        let layout = Layout::new(3, 2).unwrap();
+        let mut grid =
+            Grid::generate_with_layout(layout.clone(), Some(12), &mut rand::rng()).unwrap();
+
+        assert_eq!(grid.layout, layout);
+        assert_eq!(grid.locate_missing_box().len(), 12);
+        assert_eq!(grid.count_solutions(2), 1);
+
+        grid.solve().unwrap();
+        assert!(grid.locate_missing_box().is_empty());
+    }
+
+    #[test]
+    fn letters_in_files() {
+        let layout = Layout::for_size(16).unwrap();
+        let values = generate_with_layout(&layout, &mut rand::rng()).unwrap();
+
+        let file = TempFile::new("hexadoku");
+        let path = file.path();
+
+        write(path.clone(), values.clone()).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let read_back = read(path.clone()).unwrap();
+
+        assert!(content.contains('G'), "16 should be written as G");
+        assert_eq!(read_back, values);
+    }
+
+    #[test]
+    fn rating_needs_standard_grid() {
+        let grid = Grid::generate_with_layout(Layout::for_size(4).unwrap(), None, &mut rand::rng())
+            .unwrap();
+
+        let err = grid.rate().unwrap_err();
+
+        assert!(matches!(err, SabitaError::Unsupported(_)));
+        assert!(err
+            .to_string()
+            .contains("Rating only handles standard 9x9 grids"));
+    }
+
+    #[test]
+    fn value_out_of_small_grid() {
+        let err = Grid::new(vec![
+            vec![5, 0, 0, 0],
+            vec![0, 0, 0, 0],
+            vec![0, 0, 0, 0],
+            vec![0, 0, 0, 0],
+        ])
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            SabitaError::ValueOutOfRange {
+                value: 5,
+                line: 0,
+                column: 0
+            }
+        ));
+        assert_eq!(err.to_string(), "Value '5' out of bound at position 0;0");
+    }
+}
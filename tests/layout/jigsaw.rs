@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod jigsaw {
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::dlx::DancingLinks;
+    use sabita::core::generator::{generate_with_layout, remove_values_keeping_unique_with_layout};
+    use sabita::core::grid::{Grid, GridValues};
+    use sabita::core::layout::Layout;
+    use sabita::core::validation::{is_layout_region_valid, validate_with_layout};
+    use sabita::SabitaError;
+
+    use crate::common::TempFile;
+
+    use std::fs;
+
+    ////////////////////
+
+    /// Standard regions where 4 boxes moved around the top left corner
+    fn jigsaw_regions() -> GridValues {
+        vec![
+            vec![0, 0, 0, 0, 1, 1, 2, 2, 2],
+            vec![0, 0, 0, 1, 1, 1, 2, 2, 2],
+            vec![3, 0, 0, 1, 1, 1, 2, 2, 2],
+            vec![3, 3, 4, 4, 4, 1, 5, 5, 5],
+            vec![3, 3, 3, 4, 4, 4, 5, 5, 5],
+            vec![3, 3, 3, 4, 4, 4, 5, 5, 5],
+            vec![6, 6, 6, 7, 7, 7, 8, 8, 8],
+            vec![6, 6, 6, 7, 7, 7, 8, 8, 8],
+            vec![6, 6, 6, 7, 7, 7, 8, 8, 8],
+        ]
+    }
+
+    #[test]
+    fn valid_layout() {
+        let layout = Layout::jigsaw(&jigsaw_regions()).unwrap();
+
+        assert!(layout.is_jigsaw());
+        assert_eq!(layout.region_of(0, 3), 0);
+        assert_eq!(layout.region_of(3, 5), 1);
+        assert_eq!(layout.to_rows(), jigsaw_regions());
+    }
+
+    #[test]
+    fn invalid_layouts() {
+        let mut too_big = jigsaw_regions();
+        too_big[0][4] = 0;
+        assert!(Layout::jigsaw(&too_big).is_err());
+
+        let mut disconnected = jigsaw_regions();
+        disconnected[8][8] = 0;
+        disconnected[0][0] = 8;
+        assert!(Layout::jigsaw(&disconnected).is_err());
+
+        assert!(Layout::jigsaw(&jigsaw_regions()[1..]).is_err());
+    }
+
+    #[test]
+    fn rectangular_regions_are_recognized() {
+        let standard_rows = Layout::standard().to_rows();
+
+        assert_eq!(
+            Layout::from_regions(&standard_rows).unwrap(),
+            Layout::standard()
+        );
+        assert!(Layout::from_regions(&jigsaw_regions()).unwrap().is_jigsaw());
+    }
+
+    #[test]
+    fn regions_are_checked() {
+        let layout = Layout::jigsaw(&jigsaw_regions()).unwrap();
+        let mut values = vec![vec![TO_BE_SOLVED; 9]; 9];
+
+        // Same region for the jigsaw, different ones for the standard layout
+        values[0][3] = 4;
+        values[2][1] = 4;
+
+        assert!(!is_layout_region_valid(&values, &layout, &0).unwrap().0);
+        assert!(validate_with_layout(&values, &layout).is_err());
+        assert!(validate_with_layout(&values, &Layout::standard()).is_ok());
+    }
+
+    #[test]
+    fn generate_and_solve() {
+        let layout = Layout::jigsaw(&jigsaw_regions()).unwrap();
+
+        let full = generate_with_layout(&layout, &mut rand::rng()).unwrap();
+        assert!(validate_with_layout(&full, &layout).is_ok());
+
+        let (puzzle, _) =
+            remove_values_keeping_unique_with_layout(&full, &layout, 45, &mut rand::rng()).unwrap();
+
+        let mut grid = Grid::with_layout(puzzle, layout).unwrap();
+        assert_eq!(grid.count_solutions(2), 1);
+
+        let mut with_dlx = grid.clone();
+        with_dlx.solve_with(&DancingLinks).unwrap();
+        grid.solve().unwrap();
+
+        assert_eq!(grid.values, full);
+        assert_eq!(with_dlx.values, full);
+    }
+
+    #[test]
+    fn rating_needs_standard_regions() {
+        let layout = Layout::jigsaw(&jigsaw_regions()).unwrap();
+        let grid = Grid::generate_with_layout(layout, Some(45), &mut rand::rng()).unwrap();
+
+        let err = grid.rate().unwrap_err();
+
+        assert!(matches!(err, SabitaError::Unsupported(_)));
+        assert!(matches!(grid.canonical(), Err(SabitaError::Unsupported(_))));
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let layout = Layout::jigsaw(&jigsaw_regions()).unwrap();
+        let grid = Grid::generate_with_layout(layout.clone(), Some(40), &mut rand::rng()).unwrap();
+
+        let file = TempFile::new("jigsaw");
+        let path = file.path();
+
+        grid.dump_file(path.clone()).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let read_back = Grid::from_file(path.clone()).unwrap();
+
+        assert!(content.contains("[regions]"));
+        assert_eq!(read_back.layout, layout);
+        assert_eq!(read_back.values, grid.values);
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod layout {
+    use sabita::core::grid::{location_to_region, region_to_location};
+    use sabita::core::layout::Layout;
+    use sabita::SabitaError;
+
+    ////////////////////
+
+    #[test]
+    fn usual_layouts() {
+        for (size, box_height, box_width) in [
+            (4, 2, 2),
+            (6, 2, 3),
+            (9, 3, 3),
+            (12, 3, 4),
+            (16, 4, 4),
+            (25, 5, 5),
+        ] {
+            let layout = Layout::for_size(size).unwrap();
+
+            assert_eq!(layout.size(), size);
+            assert_eq!(layout.box_shape(), Some((box_height, box_width)));
+        }
+
+        assert!(Layout::for_size(8).is_none());
+        assert!(Layout::new(6, 6).is_err());
+        assert_eq!(Layout::for_size(9).unwrap(), Layout::standard());
+    }
+
+    #[test]
+    fn rectangular_regions() {
+        let layout = Layout::new(2, 3).unwrap();
+
+        // Two regions side by side on each band of two lines
+        assert_eq!(layout.region_of(0, 0), 0);
+        assert_eq!(layout.region_of(1, 2), 0);
+        assert_eq!(layout.region_of(0, 3), 1);
+        assert_eq!(layout.region_of(2, 0), 2);
+        assert_eq!(layout.region_of(5, 5), 5);
+
+        assert_eq!(
+            layout.region_boxes(3),
+            vec![(2, 3), (2, 4), (2, 5), (3, 3), (3, 4), (3, 5)]
+        );
+    }
+
+    #[test]
+    fn standard_regions() {
+        assert_eq!(location_to_region(&4, &7).unwrap(), 5);
+        assert_eq!(region_to_location(&7).unwrap(), (6, 3));
+        assert!(location_to_region(&9, &0).is_err());
+        assert!(matches!(
+            region_to_location(&9),
+            Err(SabitaError::Dimension(_))
+        ));
+    }
+}
@@ -0,0 +1,4 @@
+mod grid_sizes;
+mod jigsaw;
+mod layout;
+mod multigrid;
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod multigrid {
+    use sabita::core::board::value_to_mask;
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::generator::{generate_multi, remove_values_keeping_unique_multi};
+    use sabita::core::layout::Layout;
+    use sabita::core::multigrid::{is_multi_grid_file, MultiBoard, MultiGrid, MultiLayout};
+    use sabita::SabitaError;
+
+    use crate::common::TempFile;
+
+    use std::fs;
+
+    ////////////////////
+
+    /// Two 4x4 grids sharing a 2x2 region
+    fn twodoku() -> MultiLayout {
+        MultiLayout::new(Layout::for_size(4).unwrap(), vec![(0, 0), (2, 2)]).unwrap()
+    }
+
+    #[test]
+    fn samurai_layout() {
+        let layout = MultiLayout::samurai();
+
+        assert_eq!((layout.height(), layout.width()), (21, 21));
+        assert_eq!(layout.nb_grids(), 5);
+        assert_eq!(layout.grids_at(7, 7), vec![(0, 7, 7), (2, 1, 1)]);
+        assert_eq!(layout.grids_at(10, 10), vec![(2, 4, 4)]);
+        assert!(!layout.contains(0, 10));
+        assert!(!layout.contains(10, 0));
+    }
+
+    #[test]
+    fn invalid_layouts() {
+        let standard = Layout::standard();
+
+        assert!(MultiLayout::new(standard.clone(), vec![]).is_err());
+        assert!(MultiLayout::new(standard.clone(), vec![(0, 0), (4, 6)]).is_err());
+        assert!(MultiLayout::new(standard, vec![(0, 0), (0, 0)]).is_err());
+
+        let jigsaw = Layout::jigsaw(&Layout::standard().to_rows()).unwrap();
+        assert!(MultiLayout::new(jigsaw, vec![(0, 0)]).is_err());
+    }
+
+    #[test]
+    fn shared_boxes_prune_both_grids() {
+        let layout = twodoku();
+        let mut values = vec![vec![TO_BE_SOLVED; 6]; 6];
+
+        // Second grid only
+        values[5][2] = 3;
+
+        let board = MultiBoard::new(&values, &layout);
+
+        // Shared box, in the column of the value within the second grid
+        assert_eq!(board.candidates(2 * 6 + 2) & value_to_mask(3), 0);
+        // First grid only, same column of the canvas
+        assert_ne!(board.candidates(2) & value_to_mask(3), 0);
+    }
+
+    #[test]
+    fn generate_and_solve_samurai() {
+        let layout = MultiLayout::samurai();
+
+        let full = generate_multi(&layout, &mut rand::rng()).unwrap();
+        let full_grid = MultiGrid::new(full.clone(), layout.clone()).unwrap();
+        assert!(full_grid
+            .grids()
+            .unwrap()
+            .iter()
+            .all(|grid| grid.locate_missing_box().is_empty()));
+
+        let puzzle =
+            remove_values_keeping_unique_multi(&full, &layout, 200, &mut rand::rng()).unwrap();
+
+        let mut grid = MultiGrid::new(puzzle, layout).unwrap();
+        assert_eq!(grid.count_solutions(2), 1);
+
+        grid.solve().unwrap();
+        assert_eq!(grid.values, full);
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let grid = MultiGrid::generate(twodoku(), Some(12), &mut rand::rng()).unwrap();
+
+        let file = TempFile::new("multigrid");
+        let path = file.path();
+
+        grid.dump_file(path.clone()).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let is_multi_grid = is_multi_grid_file(path.clone()).unwrap();
+        let read_back = MultiGrid::from_file(path.clone()).unwrap();
+
+        assert!(content.contains("[grids]"));
+        assert!(is_multi_grid);
+        assert_eq!(read_back.layout, twodoku());
+        assert_eq!(read_back.values, grid.values);
+        assert_eq!(read_back.count_solutions(2), 1);
+    }
+
+    #[test]
+    fn values_out_of_every_grid() {
+        let mut values = vec![vec![TO_BE_SOLVED; 6]; 6];
+        values[5][0] = 1;
+
+        let err = MultiGrid::new(values, twodoku()).unwrap_err();
+
+        assert!(matches!(err, SabitaError::Dimension(_)));
+        assert_eq!(
+            err.to_string(),
+            "Value '1' at position 5;0 is out of every grid"
+        );
+    }
+}
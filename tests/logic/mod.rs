@@ -0,0 +1,2 @@
+mod rate;
+mod solve_logically;
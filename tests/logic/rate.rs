@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod rate {
+    use sabita::assets::full_grid::GRID_VALUES_1;
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::grid::Grid;
+    use sabita::core::logic::Technique;
+    use sabita::core::rating::{rate, Level, GUESSING_DIFFICULTY};
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+
+    ////////////////////
+
+    #[test]
+    fn full_grid() {
+        let rating = Grid::from_array(GRID_VALUES_1).unwrap().rate().unwrap();
+
+        assert_eq!(rating.score, 0.0);
+        assert_eq!(rating.level, Level::Easy);
+        assert_eq!(rating.hardest_technique, None);
+        assert_eq!(rating.nb_backtracks, 0);
+    }
+
+    #[test]
+    fn singles_are_easy() {
+        let mut values = grid_values_array_to_vec(GRID_VALUES_1);
+        values[0][0] = TO_BE_SOLVED;
+        values[4][4] = TO_BE_SOLVED;
+
+        let rating = rate(&values).unwrap();
+
+        assert_eq!(rating.level, Level::Easy);
+        assert_eq!(rating.hardest_technique, Some(Technique::NakedSingle));
+        assert_eq!(rating.nb_logical_steps, 2);
+        assert!(rating.solved_logically);
+    }
+
+    #[test]
+    fn guessing_is_diabolical() {
+        // Two solutions, no technique can pick one of them
+        let mut values = grid_values_array_to_vec(GRID_VALUES_1);
+        values[0][6] = TO_BE_SOLVED;
+        values[0][8] = TO_BE_SOLVED;
+        values[4][6] = TO_BE_SOLVED;
+        values[4][8] = TO_BE_SOLVED;
+
+        let rating = rate(&values).unwrap();
+
+        assert!(!rating.solved_logically);
+        assert!(rating.score >= GUESSING_DIFFICULTY);
+        assert_eq!(rating.level, Level::Diabolical);
+    }
+
+    #[test]
+    fn unsolvable() {
+        let values = vec![
+            vec![0, 1, 2, 0, 0, 9, 0, 0, 0],
+            vec![3, 4, 5, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![6, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![7, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![8, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+        ];
+
+        assert!(rate(&values).is_err());
+    }
+
+    #[test]
+    fn levels() {
+        assert_eq!(
+            Level::from_score(Technique::HiddenSingle.difficulty()),
+            Level::Easy
+        );
+        assert_eq!(
+            Level::from_score(Technique::PointingPair.difficulty()),
+            Level::Medium
+        );
+        assert_eq!(
+            Level::from_score(Technique::NakedPair.difficulty()),
+            Level::Hard
+        );
+        assert_eq!(
+            Level::from_score(Technique::XWing.difficulty()),
+            Level::Expert
+        );
+        assert_eq!(
+            Level::from_score(Technique::Jellyfish.difficulty()),
+            Level::Expert
+        );
+        assert_eq!(Level::from_score(GUESSING_DIFFICULTY), Level::Diabolical);
+
+        assert_eq!(Level::from_name("hard"), Some(Level::Hard));
+        assert_eq!(Level::from_name("impossible"), None);
+        assert_eq!(Level::Expert.to_string(), "expert");
+    }
+
+    #[test]
+    fn techniques_get_harder() {
+        for pair in Technique::ALL.windows(2) {
+            assert!(
+                pair[0].difficulty() < pair[1].difficulty(),
+                "{} vs {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+}
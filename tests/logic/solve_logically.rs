@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod solve_logically {
+    use sabita::assets::full_grid::GRID_VALUES_1;
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::generator::{remove_values_keeping_unique, seeded_rng};
+    use sabita::core::grid::Grid;
+    use sabita::core::logic::{solve_logically, LogicalSolution, Technique};
+    use sabita::core::solver::solve;
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+
+    ////////////////////
+
+    /// Every placement must match the solution and no elimination may remove it
+    fn assert_sound(logical: &LogicalSolution, solution: &[Vec<u8>]) {
+        for step in logical.steps.iter() {
+            if let Some((location, value)) = &step.placement {
+                assert_eq!(
+                    solution[location.line][location.column], *value,
+                    "Wrong placement: {step}"
+                );
+            }
+
+            for (location, value) in step.eliminations.iter() {
+                assert_ne!(
+                    solution[location.line][location.column], *value,
+                    "Wrong elimination: {step}"
+                );
+            }
+        }
+
+        for (line, row) in logical.values.iter().enumerate() {
+            for (column, value) in row.iter().enumerate() {
+                if *value != TO_BE_SOLVED {
+                    assert_eq!(solution[line][column], *value);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn full_grid_needs_no_step() {
+        let logical = solve_logically(&grid_values_array_to_vec(GRID_VALUES_1)).unwrap();
+
+        assert!(logical.is_solved());
+        assert!(logical.steps.is_empty());
+        assert_eq!(logical.hardest_technique(), None);
+    }
+
+    #[test]
+    fn last_box_is_naked_single() {
+        let mut values = grid_values_array_to_vec(GRID_VALUES_1);
+        values[8][8] = TO_BE_SOLVED;
+
+        let logical = solve_logically(&values).unwrap();
+
+        assert!(logical.is_solved());
+        assert_eq!(logical.steps.len(), 1);
+        assert_eq!(logical.steps[0].technique, Technique::NakedSingle);
+        assert_eq!(
+            logical.steps[0].to_string(),
+            "Naked single on [8:8](8): [8:8](8) = 1"
+        );
+    }
+
+    #[test]
+    fn hidden_single() {
+        // 1 has a single place left in the top left region, yet that box has other candidates
+        let values = vec![
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 1, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 1, 0, 0],
+            vec![0, 1, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 1, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+        ];
+
+        let logical = solve_logically(&values).unwrap();
+        let first = &logical.steps[0];
+
+        assert_eq!(first.technique, Technique::HiddenSingle);
+        assert_eq!(
+            first
+                .placement
+                .as_ref()
+                .map(|(loc, val)| (loc.line, loc.column, *val)),
+            Some((0, 0, 1))
+        );
+    }
+
+    #[test]
+    fn sound_on_generated_puzzles() {
+        let original = grid_values_array_to_vec(GRID_VALUES_1);
+        let mut nb_checked = 0;
+
+        for seed in 0..20 {
+            // Some removal orders can not reach that many missing values
+            let Ok((values, _)) =
+                remove_values_keeping_unique(&original, 58, &mut seeded_rng(seed))
+            else {
+                continue;
+            };
+
+            let logical = solve_logically(&values).unwrap();
+
+            assert_sound(&logical, &original);
+            nb_checked += 1;
+        }
+
+        assert!(nb_checked > 0, "No removal reached 58 missing values");
+    }
+
+    #[test]
+    fn sound_on_17_clues() {
+        let values = vec![
+            vec![0, 0, 0, 0, 0, 0, 0, 1, 0],
+            vec![4, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 2, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 5, 0, 4, 0, 7],
+            vec![0, 0, 8, 0, 0, 0, 3, 0, 0],
+            vec![0, 0, 1, 0, 9, 0, 0, 0, 0],
+            vec![3, 0, 0, 4, 0, 0, 2, 0, 0],
+            vec![0, 5, 0, 1, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 8, 0, 6, 0, 0, 0],
+        ];
+        let solution = solve(&values).unwrap();
+
+        let logical = Grid::new(values).unwrap().solve_logically().unwrap();
+
+        assert!(!logical.steps.is_empty());
+        assert_sound(&logical, &solution);
+    }
+}
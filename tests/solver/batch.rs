@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod batch {
+    use sabita::assets::full_grid::GRID_VALUES_1;
+    use sabita::core::batch::{solve_collection, solve_many, BatchOutcome, BatchReport};
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::file::{parse_line, values_to_line, write_collection};
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+
+    use crate::common::TempFile;
+
+    use std::fs;
+
+    ////////////////////
+
+    /// Full grid with a single box to find
+    fn unique(position: usize) -> Vec<Vec<u8>> {
+        let mut values = grid_values_array_to_vec(GRID_VALUES_1);
+        values[position / 9][position % 9] = TO_BE_SOLVED;
+
+        values
+    }
+
+    fn multiple() -> Vec<Vec<u8>> {
+        vec![vec![TO_BE_SOLVED; 9]; 9]
+    }
+
+    /// Two equal values on the first line
+    fn unsolvable() -> Vec<Vec<u8>> {
+        let mut values = multiple();
+        values[0][0] = 1;
+        values[0][1] = 1;
+
+        values
+    }
+
+    #[test]
+    fn outcomes_of_each_kind() {
+        let full = grid_values_array_to_vec(GRID_VALUES_1);
+        let outcomes = solve_many(&[unique(0), multiple(), unsolvable()]);
+
+        assert_eq!(
+            outcomes,
+            vec![
+                BatchOutcome::Solved(full),
+                BatchOutcome::Multiple,
+                BatchOutcome::Unsolvable
+            ]
+        );
+
+        let report = BatchReport::from_outcomes(&outcomes);
+        assert_eq!(
+            (report.solved, report.unsolvable, report.multiple),
+            (1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn outcomes_keep_input_order() {
+        let puzzles: Vec<_> = (0..500)
+            .map(|index| match index % 7 {
+                0 => multiple(),
+                _ => unique(index % 81),
+            })
+            .collect();
+
+        let outcomes = solve_many(&puzzles);
+
+        assert_eq!(outcomes.len(), puzzles.len());
+        for (index, outcome) in outcomes.iter().enumerate() {
+            match index % 7 {
+                0 => assert_eq!(*outcome, BatchOutcome::Multiple),
+                _ => assert!(matches!(outcome, BatchOutcome::Solved(_))),
+            }
+        }
+    }
+
+    #[test]
+    fn solve_collection_file() {
+        let input_file = TempFile::new("batch_input");
+        let input = input_file.path();
+        let output_file = TempFile::new("batch_output");
+        let output = output_file.path();
+
+        write_collection(input.clone(), &[unsolvable(), unique(40), multiple()]).unwrap();
+        let report = solve_collection(input.clone(), output.clone()).unwrap();
+        let content = fs::read_to_string(&output).unwrap();
+
+        assert_eq!(report.total(), 3);
+        assert_eq!(
+            (report.solved, report.unsolvable, report.multiple),
+            (1, 1, 1)
+        );
+
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            format!("{} unsolvable", values_to_line(&unsolvable()))
+        );
+        assert_eq!(
+            parse_line(lines[1]).unwrap(),
+            grid_values_array_to_vec(GRID_VALUES_1)
+        );
+        assert_eq!(
+            lines[2],
+            format!("{} multiple", values_to_line(&multiple()))
+        );
+    }
+
+    #[test]
+    fn unparsable_lines_in_collection() {
+        let input_file = TempFile::new("batch_unparsable_input");
+        let input = input_file.path();
+        let output_file = TempFile::new("batch_unparsable_output");
+        let output = output_file.path();
+
+        let content = format!(
+            "{}\ngarbage\n{}\n{}x\n",
+            values_to_line(&unique(40)),
+            values_to_line(&multiple()),
+            values_to_line(&unique(0))
+        );
+        fs::write(&input, content).unwrap();
+
+        let report = solve_collection(input.clone(), output.clone()).unwrap();
+        let content = fs::read_to_string(&output).unwrap();
+
+        assert_eq!(report.total(), 4);
+        assert_eq!((report.solved, report.multiple), (1, 1));
+        assert_eq!(report.unparsable, vec![2, 4]);
+        assert!(report.to_string().contains("(lines 2, 4)"));
+
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            parse_line(lines[0]).unwrap(),
+            grid_values_array_to_vec(GRID_VALUES_1)
+        );
+        assert_eq!(lines[1], "garbage unparsable");
+        assert_eq!(
+            lines[2],
+            format!("{} multiple", values_to_line(&multiple()))
+        );
+        assert_eq!(
+            lines[3],
+            format!("{}x unparsable", values_to_line(&unique(0)))
+        );
+    }
+
+    #[test]
+    fn missing_collection() {
+        let output_file = TempFile::new("batch_no_output");
+        let output = output_file.path();
+
+        assert!(solve_collection(TempFile::new("batch_missing").path(), output.clone()).is_err());
+        assert!(!std::path::Path::new(&output).exists());
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod board {
+    use sabita::assets::full_grid::GRID_VALUES_1;
+    use sabita::core::board::{
+        index_to_location, location_to_index, mask_to_values, pop_lowest, value_to_mask, Board,
+        ALL_CANDIDATES,
+    };
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::dlx::DancingLinks;
+    use sabita::core::grid::BoxLocation;
+    use sabita::core::logic::solve_logically;
+    use sabita::core::rating::rate;
+    use sabita::core::solver::{count_solutions, solve, Solver};
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+    use sabita::SabitaError;
+
+    ////////////////////
+
+    #[test]
+    fn round_trip() {
+        let values = grid_values_array_to_vec(GRID_VALUES_1);
+
+        assert_eq!(Board::new(&values).unwrap().get_values(), values);
+    }
+
+    #[test]
+    fn empty_board_candidates() {
+        let board = Board::new(&vec![vec![TO_BE_SOLVED; 9]; 9]).unwrap();
+
+        assert_eq!(board.candidates(0), ALL_CANDIDATES);
+        assert_eq!(board.candidates(80), ALL_CANDIDATES);
+        assert_eq!(board.missing_indices().len(), 81);
+    }
+
+    #[test]
+    fn place_and_remove() {
+        let mut board = Board::new(&vec![vec![TO_BE_SOLVED; 9]; 9]).unwrap();
+
+        board.place(0, 5);
+
+        assert_eq!(board.get(0), 5);
+        assert!(!board.can_place(8, 5), "Same line");
+        assert!(!board.can_place(72, 5), "Same column");
+        assert!(!board.can_place(20, 5), "Same region");
+        assert!(board.can_place(40, 5), "Unrelated box");
+
+        board.remove(0);
+
+        assert_eq!(board.get(0), TO_BE_SOLVED);
+        assert!(board.can_place(8, 5));
+        assert_eq!(board.candidates(20), ALL_CANDIDATES);
+    }
+
+    #[test]
+    fn candidates_of_almost_full_grid() {
+        let mut values = grid_values_array_to_vec(GRID_VALUES_1);
+        values[8][8] = TO_BE_SOLVED;
+
+        let board = Board::new(&values).unwrap();
+
+        assert_eq!(mask_to_values(board.candidates(80)), vec![1]);
+        assert_eq!(board.most_constrained(), Some((80, value_to_mask(1))));
+    }
+
+    #[test]
+    fn locations() {
+        let location = BoxLocation {
+            line: 4,
+            column: 7,
+            region: 5,
+        };
+
+        assert_eq!(location_to_index(&location), 43);
+        assert_eq!(index_to_location(43), location);
+    }
+
+    #[test]
+    fn pop_values() {
+        let mut mask = value_to_mask(2) | value_to_mask(7) | value_to_mask(9);
+
+        assert_eq!(pop_lowest(&mut mask), Some(2));
+        assert_eq!(pop_lowest(&mut mask), Some(7));
+        assert_eq!(pop_lowest(&mut mask), Some(9));
+        assert_eq!(pop_lowest(&mut mask), None);
+    }
+
+    #[test]
+    fn wrong_dimensions() {
+        let seven_lines = vec![vec![TO_BE_SOLVED; 7]; 7];
+        let mut short_line = vec![vec![TO_BE_SOLVED; 9]; 9];
+        short_line[4].truncate(3);
+        let mut out_of_bound = vec![vec![TO_BE_SOLVED; 4]; 4];
+        out_of_bound[1][1] = 5;
+
+        for values in [&seven_lines, &short_line] {
+            assert!(matches!(Board::new(values), Err(SabitaError::Dimension(_))));
+            assert!(matches!(solve(values), Err(SabitaError::Dimension(_))));
+            assert!(matches!(
+                count_solutions(values, 2),
+                Err(SabitaError::Dimension(_))
+            ));
+            assert!(DancingLinks.solve(values).is_err());
+            assert!(DancingLinks.count_solutions(values, 2).is_err());
+            assert!(solve_logically(values).is_err());
+            assert!(rate(values).is_err());
+        }
+
+        assert!(matches!(
+            Board::new(&out_of_bound),
+            Err(SabitaError::ValueOutOfRange { .. })
+        ));
+        assert!(solve_logically(&vec![vec![TO_BE_SOLVED; 4]; 4]).is_err());
+    }
+}
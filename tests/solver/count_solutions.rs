@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod count_solutions {
+    use sabita::assets::full_grid::GRID_VALUES_1;
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::grid::Grid;
+    use sabita::core::solver::count_solutions;
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+
+    ////////////////////
+
+    #[test]
+    fn full_grid_is_unique() {
+        let values = grid_values_array_to_vec(GRID_VALUES_1);
+
+        assert_eq!(count_solutions(&values, 2).unwrap(), 1);
+    }
+
+    #[test]
+    fn unique() {
+        let mut values = grid_values_array_to_vec(GRID_VALUES_1);
+        values[0][0] = TO_BE_SOLVED;
+        values[4][4] = TO_BE_SOLVED;
+        values[8][8] = TO_BE_SOLVED;
+
+        assert_eq!(count_solutions(&values, 2).unwrap(), 1);
+    }
+
+    #[test]
+    fn multiple() {
+        let mut values = grid_values_array_to_vec(GRID_VALUES_1);
+        values[0][6] = TO_BE_SOLVED;
+        values[0][8] = TO_BE_SOLVED;
+        values[4][6] = TO_BE_SOLVED;
+        values[4][8] = TO_BE_SOLVED;
+
+        assert_eq!(count_solutions(&values, 2).unwrap(), 2);
+        assert_eq!(count_solutions(&values, 10).unwrap(), 2);
+    }
+
+    #[test]
+    fn stops_at_limit() {
+        let values = vec![vec![TO_BE_SOLVED; 9]; 9];
+
+        assert_eq!(count_solutions(&values, 2).unwrap(), 2);
+        assert_eq!(count_solutions(&values, 5).unwrap(), 5);
+        assert_eq!(count_solutions(&values, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn unsolvable() {
+        // Valid so far, but the top left box can't hold any value
+        let values = vec![
+            vec![0, 1, 2, 0, 0, 9, 0, 0, 0],
+            vec![3, 4, 5, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![6, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![7, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![8, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+        ];
+
+        assert_eq!(count_solutions(&values, 2).unwrap(), 0);
+    }
+
+    #[test]
+    fn from_grid() {
+        let grid = Grid::generate(Some(40), None, &mut rand::rng()).unwrap();
+
+        assert_eq!(grid.count_solutions(2), 1);
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod dlx {
+    use sabita::assets::full_grid::GRID_VALUES_1;
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::dlx::DancingLinks;
+    use sabita::core::generator::remove_values_keeping_unique;
+    use sabita::core::grid::Grid;
+    use sabita::core::solver::{Backtracking, Engine, Solver};
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+
+    ////////////////////
+
+    fn hard_17_clues() -> Vec<Vec<u8>> {
+        vec![
+            vec![0, 0, 0, 0, 0, 0, 0, 1, 0],
+            vec![4, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 2, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 5, 0, 4, 0, 7],
+            vec![0, 0, 8, 0, 0, 0, 3, 0, 0],
+            vec![0, 0, 1, 0, 9, 0, 0, 0, 0],
+            vec![3, 0, 0, 4, 0, 0, 2, 0, 0],
+            vec![0, 5, 0, 1, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 8, 0, 6, 0, 0, 0],
+        ]
+    }
+
+    #[test]
+    fn same_solution_as_backtracking() {
+        let values = hard_17_clues();
+
+        let with_dlx = DancingLinks.solve(&values).unwrap();
+        let with_backtracking = Backtracking.solve(&values).unwrap();
+
+        assert_eq!(with_dlx, with_backtracking);
+    }
+
+    #[test]
+    fn solves_removed_values() {
+        let original = grid_values_array_to_vec(GRID_VALUES_1);
+
+        let (values, _) = remove_values_keeping_unique(&original, 55, &mut rand::rng()).unwrap();
+
+        let mut grid = Grid::new(values).unwrap();
+        grid.solve_with(&DancingLinks).unwrap();
+
+        assert_eq!(grid.get_values(), original);
+    }
+
+    #[test]
+    fn counts_solutions() {
+        let mut values = grid_values_array_to_vec(GRID_VALUES_1);
+
+        assert_eq!(DancingLinks.count_solutions(&values, 2).unwrap(), 1);
+
+        values[0][6] = TO_BE_SOLVED;
+        values[0][8] = TO_BE_SOLVED;
+        values[4][6] = TO_BE_SOLVED;
+        values[4][8] = TO_BE_SOLVED;
+
+        assert_eq!(DancingLinks.count_solutions(&values, 5).unwrap(), 2);
+        assert_eq!(
+            DancingLinks.count_solutions(&hard_17_clues(), 2).unwrap(),
+            1
+        );
+        assert_eq!(DancingLinks.count_solutions(&values, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn unsolvable() {
+        let values = vec![
+            vec![0, 1, 2, 0, 0, 9, 0, 0, 0],
+            vec![3, 4, 5, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![6, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![7, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![8, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+        ];
+
+        assert!(DancingLinks.solve(&values).is_err());
+        assert_eq!(DancingLinks.count_solutions(&values, 2).unwrap(), 0);
+    }
+
+    #[test]
+    fn conflicting_clues() {
+        let mut values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        values[0][0] = 4;
+        values[0][8] = 4;
+
+        assert!(DancingLinks.solve(&values).is_err());
+    }
+
+    #[test]
+    fn engine_names() {
+        assert_eq!(Engine::from_name("dlx"), Some(Engine::Dlx));
+        assert_eq!(Engine::from_name("backtrack"), Some(Engine::Backtrack));
+        assert_eq!(Engine::from_name("other"), None);
+        assert_eq!(Engine::default(), Engine::Backtrack);
+
+        let values = hard_17_clues();
+        assert_eq!(
+            Engine::Dlx.solver().solve(&values).unwrap(),
+            Engine::Backtrack.solver().solve(&values).unwrap()
+        );
+    }
+}
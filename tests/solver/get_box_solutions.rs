@@ -3,6 +3,9 @@ mod get_box_solutions {
     use sabita::core::constants::TO_BE_SOLVED;
     use sabita::core::grid::BoxLocation;
     use sabita::core::solver::get_box_solutions;
+    use sabita::SabitaError;
+
+    ////////////////////
 
     #[test]
     fn should_be_1() {
@@ -116,12 +119,25 @@ mod get_box_solutions {
 
         let answers = get_box_solutions(&values, &location);
 
-        match answers {
-            Ok(_) => assert!(false, "Should have thrown error"),
-            Err(err) => assert_eq!(
-                err.to_string(),
-                "Solution of the box [0:0](0) could'nt be found"
-            ),
-        }
+        assert!(matches!(answers, Err(SabitaError::Unsolvable)));
+    }
+
+    #[test]
+    fn box_out_of_grid() {
+        let values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        let location = BoxLocation {
+            line: 9,
+            column: 0,
+            region: 6,
+        };
+
+        assert!(matches!(
+            get_box_solutions(&values, &location),
+            Err(SabitaError::Dimension(_))
+        ));
+        assert!(matches!(
+            get_box_solutions(&vec![vec![TO_BE_SOLVED; 7]; 7], &location),
+            Err(SabitaError::Dimension(_))
+        ));
     }
 }
@@ -1,2 +1,7 @@
+mod batch;
+mod board;
+mod count_solutions;
+mod dlx;
 mod get_box_solutions;
+mod solutions;
 mod solver_e2e;
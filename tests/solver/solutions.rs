@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod solutions {
+    use sabita::assets::full_grid::GRID_VALUES_1;
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::grid::Grid;
+    use sabita::core::solver::Solutions;
+    use sabita::core::validation::validate;
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+
+    ////////////////////
+
+    #[test]
+    fn full_grid() {
+        let grid = Grid::from_array(GRID_VALUES_1).unwrap();
+
+        let solutions: Vec<Grid> = grid.solutions().collect();
+
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].get_values(), grid.get_values());
+    }
+
+    #[test]
+    fn unique() {
+        let original = grid_values_array_to_vec(GRID_VALUES_1);
+
+        let mut values = original.clone();
+        values[0][0] = TO_BE_SOLVED;
+        values[3][5] = TO_BE_SOLVED;
+        values[7][2] = TO_BE_SOLVED;
+
+        let solutions: Vec<Grid> = Grid::new(values).unwrap().solutions().collect();
+
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].get_values(), original);
+    }
+
+    #[test]
+    fn every_solution() {
+        let original = grid_values_array_to_vec(GRID_VALUES_1);
+
+        let mut values = original.clone();
+        values[0][6] = TO_BE_SOLVED;
+        values[0][8] = TO_BE_SOLVED;
+        values[4][6] = TO_BE_SOLVED;
+        values[4][8] = TO_BE_SOLVED;
+
+        let solutions: Vec<Grid> = Grid::new(values).unwrap().solutions().collect();
+
+        assert_eq!(solutions.len(), 2, "Bad number of solutions");
+        assert_ne!(solutions[0].get_values(), solutions[1].get_values());
+        assert!(solutions.iter().any(|sol| sol.get_values() == original));
+
+        for solution in solutions {
+            assert!(validate(&solution.get_values()).is_ok(), "Invalid solution");
+        }
+    }
+
+    #[test]
+    fn unsolvable() {
+        let values = vec![
+            vec![0, 1, 2, 0, 0, 9, 0, 0, 0],
+            vec![3, 4, 5, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![6, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![7, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![8, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0],
+        ];
+
+        assert_eq!(Solutions::new(&values).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn lazy_on_empty_grid() {
+        let values = vec![vec![TO_BE_SOLVED; 9]; 9];
+
+        let solutions: Vec<_> = Solutions::new(&values).unwrap().take(3).collect();
+
+        assert_eq!(solutions.len(), 3);
+        assert_ne!(solutions[0], solutions[1]);
+        assert_ne!(solutions[1], solutions[2]);
+
+        for solution in solutions {
+            assert!(validate(&solution).is_ok(), "Invalid solution");
+            assert!(!solution.iter().flatten().any(|&val| val == TO_BE_SOLVED));
+        }
+    }
+}
@@ -6,13 +6,15 @@ mod solver_e2e {
     use sabita::core::validation::validate;
     use sabita::utils::grid_utils::grid_values_array_to_vec;
 
-    fn solve_test(nb_missing: u8) {
+    fn solve_test(nb_missing: u16) {
         let original = grid_values_array_to_vec(GRID_VALUES_1);
 
-        let mut to_solve = Grid::new(original.clone());
-        to_solve.remove_random_values(nb_missing);
+        let mut to_solve = Grid::new(original.clone()).unwrap();
+        to_solve
+            .remove_random_values(nb_missing, &mut rand::rng())
+            .unwrap();
 
-        to_solve.solve();
+        to_solve.solve().unwrap();
         let res = to_solve.get_values();
 
         assert!(validate(&res).is_ok(), "Grid isn't valid");
@@ -49,4 +51,27 @@ mod solver_e2e {
     fn can_solve_80_missing() {
         solve_test(80);
     }
+
+    #[test]
+    fn can_solve_17_clues() {
+        let mut grid = Grid::new(vec![
+            vec![0, 0, 0, 0, 0, 0, 0, 1, 0],
+            vec![4, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 2, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 5, 0, 4, 0, 7],
+            vec![0, 0, 8, 0, 0, 0, 3, 0, 0],
+            vec![0, 0, 1, 0, 9, 0, 0, 0, 0],
+            vec![3, 0, 0, 4, 0, 0, 2, 0, 0],
+            vec![0, 5, 0, 1, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 8, 0, 6, 0, 0, 0],
+        ])
+        .unwrap();
+
+        assert_eq!(grid.count_solutions(2), 1, "Puzzle should be unique");
+
+        grid.solve().unwrap();
+
+        assert!(validate(&grid.get_values()).is_ok(), "Grid isn't valid");
+        assert!(grid.locate_missing_box().is_empty(), "Unsolved boxes left");
+    }
 }
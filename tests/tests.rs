@@ -1,3 +1,11 @@
+#![allow(clippy::module_inception, clippy::assertions_on_constants)]
+
+pub mod common;
+pub mod constraint;
+pub mod file;
 pub mod generate;
+pub mod layout;
+pub mod logic;
 pub mod solver;
+pub mod transform;
 pub mod validation;
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod canonical {
+    use sabita::assets::full_grid::{GRID_VALUES_1, GRID_VALUES_2};
+    use sabita::core::canonical::{canonical_form, fingerprint};
+    use sabita::core::generator::remove_values_keeping_unique;
+    use sabita::core::grid::Grid;
+    use sabita::core::transform::Transformation;
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+
+    ////////////////////
+
+    #[test]
+    fn equivalent_puzzles_share_it() {
+        let values = grid_values_array_to_vec(GRID_VALUES_1);
+        let (puzzle, _) = remove_values_keeping_unique(&values, 50, &mut rand::rng()).unwrap();
+
+        let (canonical, _) = canonical_form(&puzzle);
+
+        for _ in 0..10 {
+            let variant = Transformation::random(&mut rand::rng()).apply(&puzzle);
+
+            assert_eq!(canonical_form(&variant).0, canonical);
+            assert_eq!(fingerprint(&variant), fingerprint(&puzzle));
+        }
+    }
+
+    #[test]
+    fn returned_transformation_leads_to_it() {
+        let values = grid_values_array_to_vec(GRID_VALUES_2);
+        let (puzzle, _) = remove_values_keeping_unique(&values, 45, &mut rand::rng()).unwrap();
+
+        let (canonical, transformation) = canonical_form(&puzzle);
+
+        assert_eq!(transformation.apply(&puzzle), canonical);
+    }
+
+    #[test]
+    fn idempotent() {
+        let grid = Grid::new(grid_values_array_to_vec(GRID_VALUES_1)).unwrap();
+
+        let canonical = grid.canonical().unwrap();
+
+        assert_eq!(canonical.canonical().unwrap().values, canonical.values);
+        assert_eq!(
+            canonical.fingerprint().unwrap(),
+            grid.fingerprint().unwrap()
+        );
+        assert_eq!(grid.fingerprint().unwrap().len(), 16);
+    }
+
+    #[test]
+    fn different_puzzles_differ() {
+        let first = Grid::new(grid_values_array_to_vec(GRID_VALUES_1)).unwrap();
+        let second = Grid::new(grid_values_array_to_vec(GRID_VALUES_2)).unwrap();
+
+        assert_ne!(first.fingerprint().unwrap(), second.fingerprint().unwrap());
+    }
+}
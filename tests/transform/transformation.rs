@@ -0,0 +1,176 @@
+#[cfg(test)]
+mod transformation {
+    use sabita::assets::full_grid::{GRID_VALUES_1, GRID_VALUES_2};
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::grid::{Grid, GridValues};
+    use sabita::core::solver::count_solutions;
+    use sabita::core::transform::Transformation;
+    use sabita::core::validation::validate;
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+    use sabita::SabitaError;
+
+    ////////////////////
+
+    fn every_move() -> Vec<Transformation> {
+        vec![
+            Transformation::identity(),
+            Transformation::permute_lines_in_band(1, [2, 0, 1]).unwrap(),
+            Transformation::permute_bands([1, 2, 0]).unwrap(),
+            Transformation::permute_columns_in_stack(2, [1, 0, 2]).unwrap(),
+            Transformation::permute_stacks([2, 1, 0]).unwrap(),
+            Transformation::swap_lines(6, 8).unwrap(),
+            Transformation::swap_columns(0, 1).unwrap(),
+            Transformation::transpose(),
+            Transformation::rotate_clockwise(),
+            Transformation::rotate_counter_clockwise(),
+            Transformation::rotate_half_turn(),
+            Transformation::reflect_horizontally(),
+            Transformation::reflect_vertically(),
+            Transformation::reflect_anti_diagonally(),
+            Transformation::relabel([9, 8, 7, 6, 5, 4, 3, 2, 1]).unwrap(),
+            Transformation::random(&mut rand::rng()),
+        ]
+    }
+
+    #[test]
+    fn keeps_grids_valid() {
+        let values = grid_values_array_to_vec(GRID_VALUES_1);
+
+        for transformation in every_move() {
+            let transformed = transformation.apply(&values);
+
+            assert!(validate(&transformed).is_ok(), "{transformation:?}");
+        }
+    }
+
+    #[test]
+    fn inverse_undoes() {
+        let values = grid_values_array_to_vec(GRID_VALUES_2);
+
+        for transformation in every_move() {
+            let transformed = transformation.apply(&values);
+
+            assert_eq!(transformation.inverse().apply(&transformed), values);
+            assert_eq!(
+                transformation.then(&transformation.inverse()),
+                Transformation::identity()
+            );
+        }
+    }
+
+    #[test]
+    fn composition() {
+        let values = grid_values_array_to_vec(GRID_VALUES_1);
+
+        for first in every_move() {
+            for second in every_move() {
+                let one_by_one = second.apply(&first.apply(&values));
+
+                assert_eq!(first.then(&second).apply(&values), one_by_one);
+            }
+        }
+    }
+
+    #[test]
+    fn rotations() {
+        let values = grid_values_array_to_vec(GRID_VALUES_1);
+        let rotated = Transformation::rotate_clockwise().apply(&values);
+
+        // First column read bottom up becomes the first line
+        let expected_first_line: Vec<u8> = values.iter().rev().map(|line| line[0]).collect();
+        assert_eq!(rotated[0], expected_first_line);
+
+        let quarter = Transformation::rotate_clockwise();
+        let full_turn = quarter.then(&quarter).then(&quarter).then(&quarter);
+        assert_eq!(full_turn, Transformation::identity());
+
+        assert_eq!(
+            quarter.then(&quarter),
+            Transformation::rotate_half_turn(),
+            "Two quarters make a half turn"
+        );
+        assert_eq!(
+            quarter.inverse(),
+            Transformation::rotate_counter_clockwise()
+        );
+    }
+
+    #[test]
+    fn reflections() {
+        let values = grid_values_array_to_vec(GRID_VALUES_1);
+
+        let mirrored = Transformation::reflect_horizontally().apply(&values);
+        let mut expected_first_line = values[0].clone();
+        expected_first_line.reverse();
+        assert_eq!(mirrored[0], expected_first_line);
+
+        let transposed = Transformation::transpose().apply(&values);
+        assert_eq!(transposed[2][7], values[7][2]);
+
+        let anti = Transformation::reflect_anti_diagonally().apply(&values);
+        assert_eq!(anti[0][0], values[8][8]);
+        assert_eq!(anti[1][0], values[8][7]);
+    }
+
+    #[test]
+    fn relabeling_keeps_blanks() {
+        let mut values: GridValues = grid_values_array_to_vec(GRID_VALUES_1);
+        values[0][0] = TO_BE_SOLVED;
+
+        let relabeled = Transformation::relabel([2, 1, 3, 4, 5, 6, 7, 8, 9])
+            .unwrap()
+            .apply(&values);
+
+        assert_eq!(relabeled[0][0], TO_BE_SOLVED);
+        assert_eq!(relabeled[0][1], values[0][1]);
+        assert_eq!(relabeled[0][2], 2, "1 became 2");
+    }
+
+    #[test]
+    fn puzzles_keep_their_solution_count() {
+        let grid = Grid::generate(Some(50), None, &mut rand::rng()).unwrap();
+        let transformation = Transformation::random(&mut rand::rng());
+
+        let transformed = grid.transform(&transformation).unwrap();
+
+        assert_eq!(count_solutions(&transformed.get_values(), 2).unwrap(), 1);
+
+        let mut solved = transformed.clone();
+        solved.solve().unwrap();
+
+        let mut original_solved = grid.clone();
+        original_solved.solve().unwrap();
+
+        assert_eq!(
+            transformation.inverse().apply(&solved.get_values()),
+            original_solved.get_values()
+        );
+    }
+
+    #[test]
+    fn lines_of_different_bands() {
+        let err = Transformation::swap_lines(2, 3).unwrap_err();
+
+        assert!(matches!(err, SabitaError::Dimension(_)));
+        assert_eq!(err.to_string(), "Lines 2 and 3 are not in the same band");
+        assert!(Transformation::swap_lines(9, 10).is_err());
+        assert!(Transformation::swap_columns(4, 9).is_err());
+    }
+
+    #[test]
+    fn relabel_twice() {
+        let err = Transformation::relabel([3, 3, 1, 4, 5, 6, 7, 8, 9]).unwrap_err();
+
+        assert!(matches!(err, SabitaError::Dimension(_)));
+        assert_eq!(err.to_string(), "Value '3' used twice in the relabeling");
+        assert!(Transformation::relabel([10, 2, 1, 4, 5, 6, 7, 8, 9]).is_err());
+    }
+
+    #[test]
+    fn wrong_permutations() {
+        assert!(Transformation::permute_bands([0, 0, 1]).is_err());
+        assert!(Transformation::permute_stacks([0, 1, 3]).is_err());
+        assert!(Transformation::permute_lines_in_band(3, [0, 1, 2]).is_err());
+        assert!(Transformation::permute_columns_in_stack(0, [2, 2, 2]).is_err());
+    }
+}
@@ -1 +1,2 @@
+mod validate_all;
 mod validate_new_box;
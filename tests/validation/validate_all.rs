@@ -0,0 +1,178 @@
+#[cfg(test)]
+mod validate_all {
+    use sabita::assets::full_grid::GRID_VALUES_1;
+    use sabita::core::constants::TO_BE_SOLVED;
+    use sabita::core::constraint::{AntiKnight, Constraint, Diagonal, Parity, ParityCells};
+    use sabita::core::grid::BoxLocation;
+    use sabita::core::killer::{Cage, Killer};
+    use sabita::core::layout::Layout;
+    use sabita::core::validation::{
+        validate, validate_all, validate_all_with_constraints, ValidationErrorType,
+    };
+    use sabita::utils::grid_utils::grid_values_array_to_vec;
+    use sabita::SabitaError;
+
+    use std::sync::Arc;
+
+    ////////////////////
+
+    fn location(line: usize, column: usize) -> BoxLocation {
+        BoxLocation {
+            line,
+            column,
+            region: Layout::standard().region_of(line, column),
+        }
+    }
+
+    #[test]
+    fn valid_grid() {
+        let values = grid_values_array_to_vec(GRID_VALUES_1);
+
+        assert!(validate_all(&values).unwrap().is_empty());
+    }
+
+    #[test]
+    fn every_conflict_listed() {
+        let mut values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        values[0][0] = 1;
+        values[0][8] = 1;
+        values[0][1] = 2;
+        values[1][0] = 2;
+        values[8][8] = 3;
+        values[7][8] = 3;
+
+        let conflicts = validate_all(&values).unwrap();
+        let zones: Vec<(ValidationErrorType, usize, usize)> = conflicts
+            .iter()
+            .map(|err| (err.err_type().clone(), err.index(), err.value()))
+            .collect();
+
+        assert_eq!(
+            zones,
+            vec![
+                (ValidationErrorType::LINE, 0, 1),
+                (ValidationErrorType::COLUMN, 8, 3),
+                (ValidationErrorType::REGION, 0, 2),
+                (ValidationErrorType::REGION, 8, 3),
+            ]
+        );
+
+        assert_eq!(conflicts[0].cells(), &[location(0, 0), location(0, 8)]);
+        assert_eq!(conflicts[2].cells(), &[location(0, 1), location(1, 0)]);
+    }
+
+    #[test]
+    fn value_repeated_three_times() {
+        let mut values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        values[4][0] = 5;
+        values[4][4] = 5;
+        values[4][8] = 5;
+
+        let conflicts = validate_all(&values).unwrap();
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0].cells(),
+            &[location(4, 0), location(4, 4), location(4, 8)]
+        );
+    }
+
+    #[test]
+    fn first_conflict_has_cells() {
+        let mut values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        values[2][3] = 7;
+        values[6][3] = 7;
+
+        let Err(SabitaError::Conflict(err)) = validate(&values) else {
+            panic!("A repeated value should be a conflict");
+        };
+
+        assert_eq!(err.err_type(), &ValidationErrorType::COLUMN);
+        assert_eq!(err.cells(), &[location(2, 3), location(6, 3)]);
+    }
+
+    #[test]
+    fn constraint_conflicts() {
+        let mut values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        values[0][0] = 4;
+        values[8][8] = 4;
+
+        let constraints: Vec<Arc<dyn Constraint>> = vec![Arc::new(Diagonal)];
+        let conflicts =
+            validate_all_with_constraints(&values, &Layout::standard(), &constraints).unwrap();
+
+        assert_eq!(conflicts.len(), 2);
+        assert!(conflicts
+            .iter()
+            .all(|err| err.err_type() == &ValidationErrorType::CONSTRAINT("diagonal".to_string())));
+        assert_eq!(conflicts[0].cells(), &[location(0, 0), location(8, 8)]);
+        assert_eq!(conflicts[1].cells(), &[location(0, 0), location(8, 8)]);
+    }
+
+    #[test]
+    fn constraint_conflict_partners() {
+        let layout = Layout::standard();
+        let mut values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        values[4][4] = 6;
+        values[2][3] = 6;
+        values[6][5] = 6;
+        values[0][8] = 3;
+
+        let cage = Cage::new(vec![0, 1, 2], 6);
+        let constraints: Vec<Arc<dyn Constraint>> = vec![
+            Arc::new(AntiKnight),
+            Arc::new(Killer::new(&layout, vec![cage]).unwrap()),
+            Arc::new(ParityCells::new(9, vec![(8, Parity::Even)])),
+        ];
+        let conflicts = validate_all_with_constraints(&values, &layout, &constraints).unwrap();
+
+        let knight = conflicts
+            .iter()
+            .find(|err| err.index() == 40)
+            .expect("Knight move conflict");
+        assert_eq!(
+            knight.cells(),
+            &[location(2, 3), location(4, 4), location(6, 5)]
+        );
+
+        let parity = conflicts
+            .iter()
+            .find(|err| err.index() == 8)
+            .expect("Parity conflict");
+        assert_eq!(parity.cells(), &[location(0, 8)]);
+
+        values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        values[0][0] = 1;
+        values[0][1] = 4;
+        values[0][2] = 3;
+        let conflicts = validate_all_with_constraints(&values, &layout, &constraints).unwrap();
+
+        assert_eq!(conflicts.len(), 3);
+        assert!(conflicts
+            .iter()
+            .all(|err| err.cells() == [location(0, 0), location(0, 1), location(0, 2)]));
+    }
+
+    #[test]
+    fn wrong_dimensions() {
+        let seven_lines = vec![vec![TO_BE_SOLVED; 7]; 7];
+        assert!(matches!(
+            validate_all(&seven_lines),
+            Err(SabitaError::Dimension(_))
+        ));
+
+        let mut short_line = vec![vec![TO_BE_SOLVED; 9]; 9];
+        short_line[3].truncate(5);
+        assert!(matches!(
+            validate_all(&short_line),
+            Err(SabitaError::Dimension(_))
+        ));
+
+        let mut out_of_bound = vec![vec![TO_BE_SOLVED; 9]; 9];
+        out_of_bound[2][2] = 10;
+        assert!(matches!(
+            validate_all(&out_of_bound),
+            Err(SabitaError::ValueOutOfRange { .. })
+        ));
+    }
+}
@@ -1,7 +1,11 @@
 #[cfg(test)]
 mod validate_new_box {
+    use sabita::core::constants::TO_BE_SOLVED;
     use sabita::core::grid::BoxLocation;
-    use sabita::core::validation::validate_new_box;
+    use sabita::core::validation::{
+        is_column_valid, is_line_valid, is_region_valid, validate, validate_new_box,
+    };
+    use sabita::SabitaError;
 
     #[test]
     fn valid_1() {
@@ -98,4 +102,52 @@ mod validate_new_box {
             ),
         }
     }
+
+    #[test]
+    fn wrong_dimensions() {
+        let seven_lines = vec![vec![TO_BE_SOLVED; 7]; 7];
+        assert!(matches!(
+            validate(&seven_lines),
+            Err(SabitaError::Dimension(_))
+        ));
+        assert!(matches!(
+            is_region_valid(&seven_lines, &0),
+            Err(SabitaError::Dimension(_))
+        ));
+
+        let mut short_line = vec![vec![TO_BE_SOLVED; 9]; 9];
+        short_line[3].truncate(5);
+        assert!(matches!(
+            validate(&short_line),
+            Err(SabitaError::Dimension(_))
+        ));
+        assert!(matches!(
+            is_column_valid(&short_line, &6),
+            Err(SabitaError::Dimension(_))
+        ));
+    }
+
+    #[test]
+    fn index_out_of_bound() {
+        let values = vec![vec![TO_BE_SOLVED; 9]; 9];
+        let location = BoxLocation {
+            line: 9,
+            column: 0,
+            region: 0,
+        };
+
+        assert!(matches!(
+            validate_new_box(&values, &location),
+            Err(SabitaError::Dimension(_))
+        ));
+        assert!(matches!(
+            is_line_valid(&values, &9),
+            Err(SabitaError::Dimension(_))
+        ));
+        assert!(matches!(
+            is_region_valid(&values, &9),
+            Err(SabitaError::Dimension(_))
+        ));
+        assert_eq!(is_line_valid(&values, &8).unwrap(), (true, None));
+    }
 }